@@ -0,0 +1,6 @@
+-- 썸네일 변환본 목록을 글에 둡니다. 이미 있는 글은 POST /posts/{id}/thumbnail/variants 로 다시 만들 수 있습니다.
+BEGIN;
+
+ALTER TABLE public.posts ADD COLUMN IF NOT EXISTS thumbnail_variants JSONB NOT NULL DEFAULT '[]';
+
+COMMIT;
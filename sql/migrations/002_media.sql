@@ -0,0 +1,28 @@
+-- 업로드한 파일을 media 에 기록하고 어느 글에서 쓰는지 추적합니다.
+-- 이미 스토리지에 있는 파일은 기록이 없으므로 고아 정리 대상이 되지 않습니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.media (
+  id               SERIAL           PRIMARY KEY,
+  key              TEXT             NOT NULL UNIQUE,
+  hash             TEXT             NOT NULL,
+  url              TEXT             NOT NULL,
+  filename         TEXT             NOT NULL DEFAULT '',
+  content_type     TEXT             NOT NULL,
+  width            INTEGER          NOT NULL,
+  height           INTEGER          NOT NULL,
+  size             BIGINT           NOT NULL,
+  placeholder      TEXT             NOT NULL DEFAULT '',
+  variants         JSONB            NOT NULL DEFAULT '[]',
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS media_hash_idx ON public.media (hash);
+
+CREATE TABLE IF NOT EXISTS public.media_usages (
+  media_id         INTEGER          NOT NULL REFERENCES public.media (id) ON DELETE CASCADE,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  PRIMARY KEY (media_id, post_id)
+);
+
+COMMIT;
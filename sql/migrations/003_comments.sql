@@ -0,0 +1,21 @@
+-- 댓글과 대댓글을 저장합니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.comments (
+  id               SERIAL           PRIMARY KEY,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  parent_id        INTEGER          REFERENCES public.comments (id) ON DELETE CASCADE,
+  nickname         VARCHAR(30)      NOT NULL,
+  password_hash    TEXT             NOT NULL DEFAULT '',
+  body             TEXT             NOT NULL,
+  is_author        BOOLEAN          NOT NULL DEFAULT FALSE,
+  status           VARCHAR(16)      NOT NULL DEFAULT 'approved',
+  deleted          BOOLEAN          NOT NULL DEFAULT FALSE,
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW(),
+  updated_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS comments_post_id_idx ON public.comments (post_id, created_at);
+CREATE INDEX IF NOT EXISTS comments_status_idx ON public.comments (status, created_at);
+
+COMMIT;
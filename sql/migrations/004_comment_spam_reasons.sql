@@ -0,0 +1,6 @@
+-- 검토 대기로 보낸 이유를 댓글에 남깁니다. 이미 있는 댓글은 이유가 비어 있습니다.
+BEGIN;
+
+ALTER TABLE public.comments ADD COLUMN IF NOT EXISTS spam_reasons TEXT[] NOT NULL DEFAULT '{}';
+
+COMMIT;
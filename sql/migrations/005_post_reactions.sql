@@ -0,0 +1,20 @@
+-- like_count 를 이모지 반응으로 옮깁니다. 기존 좋아요는 기본 반응(👍)으로 합산됩니다.
+BEGIN;
+
+ALTER TABLE public.posts ADD COLUMN IF NOT EXISTS reactions JSONB NOT NULL DEFAULT '{}';
+
+CREATE TABLE IF NOT EXISTS public.post_reactions (
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  emoji            TEXT             NOT NULL,
+  visitor_id       TEXT             NOT NULL,
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (post_id, visitor_id, emoji)
+);
+
+UPDATE public.posts
+SET reactions = jsonb_set(reactions, '{👍}', to_jsonb(COALESCE((reactions->>'👍')::int, 0) + like_count))
+WHERE like_count > 0;
+
+ALTER TABLE public.posts DROP COLUMN like_count;
+
+COMMIT;
@@ -0,0 +1,20 @@
+-- 방문자 중복 판별용 기록. 하루 단위 salt 로 해시하며 날짜가 바뀌면 지웁니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.visitor_salts (
+  day              DATE             PRIMARY KEY,
+  salt             TEXT             NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS public.visitor_hits (
+  scope            TEXT             NOT NULL,
+  post_id          INTEGER          NOT NULL,
+  fingerprint      TEXT             NOT NULL,
+  visitor_id       TEXT             NOT NULL DEFAULT '',
+  day              DATE             NOT NULL,
+  PRIMARY KEY (scope, post_id, fingerprint)
+);
+
+CREATE INDEX IF NOT EXISTS visitor_hits_day_idx ON public.visitor_hits (day);
+
+COMMIT;
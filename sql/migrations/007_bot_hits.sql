@@ -0,0 +1,12 @@
+-- 봇으로 판단해 조회수에서 뺀 요청을 이유별로 셉니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.bot_hits (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  reason           TEXT             NOT NULL,
+  hits             INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id, reason)
+);
+
+COMMIT;
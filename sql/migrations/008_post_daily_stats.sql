@@ -0,0 +1,15 @@
+-- 글별 일간 통계. 이전 기록은 없으므로 마이그레이션한 날부터 쌓입니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.post_daily_stats (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  views            INTEGER          NOT NULL DEFAULT 0,
+  visitors         INTEGER          NOT NULL DEFAULT 0,
+  reactions        INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id)
+);
+
+CREATE INDEX IF NOT EXISTS post_daily_stats_post_idx ON public.post_daily_stats (post_id, day);
+
+COMMIT;
@@ -0,0 +1,23 @@
+-- 유입 경로와 utm 캠페인을 그날 처음 본 방문자 기준으로 셉니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.view_sources (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  source           VARCHAR(16)      NOT NULL,
+  referrer_host    TEXT             NOT NULL DEFAULT '',
+  hits             INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id, source, referrer_host)
+);
+
+CREATE TABLE IF NOT EXISTS public.view_campaigns (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  utm_source       TEXT             NOT NULL,
+  utm_medium       TEXT             NOT NULL DEFAULT '',
+  utm_campaign     TEXT             NOT NULL DEFAULT '',
+  hits             INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id, utm_source, utm_medium, utm_campaign)
+);
+
+COMMIT;
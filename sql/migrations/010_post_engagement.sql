@@ -0,0 +1,13 @@
+-- 읽기 지표는 방문자마다 하루 한 번 보낸 비콘으로 셉니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.post_engagement (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  reads            INTEGER          NOT NULL DEFAULT 0,
+  read_secs        BIGINT           NOT NULL DEFAULT 0,
+  completions      INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id)
+);
+
+COMMIT;
@@ -0,0 +1,7 @@
+-- 글에 초안/발행 상태를 둡니다. 이미 있는 글은 모두 발행된 것으로 봅니다.
+BEGIN;
+
+ALTER TABLE public.posts ADD COLUMN IF NOT EXISTS status VARCHAR(16) NOT NULL DEFAULT 'published'
+  CHECK (status IN ('draft', 'published'));
+
+COMMIT;
@@ -0,0 +1,19 @@
+-- 글을 시리즈로 묶습니다. 글은 한 시리즈에만 속하고 position 은 1 부터 시작하는 편 번호입니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.series (
+  id               SERIAL           PRIMARY KEY,
+  title            VARCHAR(255)     NOT NULL,
+  slug             VARCHAR(100)     NOT NULL UNIQUE,
+  description      TEXT             NOT NULL DEFAULT '',
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS public.series_posts (
+  post_id          INTEGER          PRIMARY KEY REFERENCES public.posts (id) ON DELETE CASCADE,
+  series_id        INTEGER          NOT NULL REFERENCES public.series (id) ON DELETE CASCADE,
+  position         INTEGER          NOT NULL,
+  UNIQUE (series_id, position)
+);
+
+COMMIT;
@@ -0,0 +1,20 @@
+-- 계층형 카테고리를 두고 글마다 대표 카테고리를 하나 고릅니다. 이미 있는 글은 미분류로 남습니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.categories (
+  id               SERIAL           PRIMARY KEY,
+  parent_id        INTEGER          REFERENCES public.categories (id),
+  name             VARCHAR(100)     NOT NULL,
+  slug             VARCHAR(100)     NOT NULL UNIQUE,
+  position         INTEGER          NOT NULL DEFAULT 0,
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS categories_parent_idx ON public.categories (parent_id);
+
+ALTER TABLE public.posts ADD COLUMN IF NOT EXISTS category_id INTEGER
+  REFERENCES public.categories (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS posts_category_idx ON public.posts (category_id);
+
+COMMIT;
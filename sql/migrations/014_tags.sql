@@ -0,0 +1,42 @@
+-- 태그를 tags 테이블로 옮기고 posts.tags 에 저장된 값을 정규화합니다.
+-- "Rust" 와 "rust" 처럼 표기만 다른 태그는 하나로 합쳐지고, 처음 보는 표기를 이름으로 씁니다.
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS public.tags (
+  id               SERIAL           PRIMARY KEY,
+  slug             VARCHAR(50)      NOT NULL UNIQUE,
+  name             VARCHAR(50)      NOT NULL,
+  description      TEXT             NOT NULL DEFAULT '',
+  color            VARCHAR(7)       NOT NULL DEFAULT '',
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+-- tag::service::normalize 와 같은 규칙입니다.
+CREATE FUNCTION pg_temp.normalize_tag(tag TEXT) RETURNS TEXT LANGUAGE sql IMMUTABLE AS $$
+  SELECT btrim(left(btrim(regexp_replace(lower(btrim(tag)), '[[:space:][:cntrl:]_,/-]+', '-', 'g'), '-'), 50), '-')
+$$;
+
+INSERT INTO public.tags (slug, name)
+SELECT DISTINCT ON (slug) slug, left(btrim(tag), 50)
+FROM (
+  SELECT tag, pg_temp.normalize_tag(tag) AS slug
+  FROM public.posts, unnest(tags) AS tag
+) t
+WHERE slug <> ''
+ORDER BY slug, tag
+ON CONFLICT (slug) DO NOTHING;
+
+-- 글 안에서 정규화 후 겹치는 태그는 처음 나온 순서대로 하나만 남깁니다.
+UPDATE public.posts p
+SET tags = COALESCE((
+  SELECT array_agg(slug ORDER BY first_pos)
+  FROM (
+    SELECT pg_temp.normalize_tag(tag) AS slug, MIN(pos) AS first_pos
+    FROM unnest(p.tags) WITH ORDINALITY AS t(tag, pos)
+    GROUP BY 1
+  ) n
+  WHERE slug <> ''
+), '{}')
+WHERE cardinality(tags) > 0;
+
+COMMIT;
@@ -0,0 +1,16 @@
+-- 글 목록의 태그 조건과 정렬에 쓰는 인덱스입니다.
+-- 좋아요순 정렬은 JSONB 를 매번 풀지 않도록 반응 합계를 reaction_total 에 따로 둡니다.
+BEGIN;
+
+ALTER TABLE public.posts ADD COLUMN IF NOT EXISTS reaction_total INTEGER NOT NULL DEFAULT 0;
+
+UPDATE public.posts
+SET reaction_total = (SELECT COALESCE(SUM(value::int), 0) FROM jsonb_each_text(reactions))
+WHERE reactions <> '{}';
+
+CREATE INDEX IF NOT EXISTS posts_tags_idx ON public.posts USING GIN (tags);
+CREATE INDEX IF NOT EXISTS posts_created_idx ON public.posts (created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS posts_view_count_idx ON public.posts (view_count DESC);
+CREATE INDEX IF NOT EXISTS posts_reaction_total_idx ON public.posts (reaction_total DESC, created_at DESC, id DESC);
+
+COMMIT;
@@ -1,19 +1,182 @@
 DROP SCHEMA IF EXISTS public CASCADE;
 CREATE SCHEMA public;
 
+CREATE TABLE public.categories (
+  id               SERIAL           PRIMARY KEY,
+  parent_id        INTEGER          REFERENCES public.categories (id),
+  name             VARCHAR(100)     NOT NULL,
+  slug             VARCHAR(100)     NOT NULL UNIQUE,
+  position         INTEGER          NOT NULL DEFAULT 0,
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX categories_parent_idx ON public.categories (parent_id);
+
 CREATE TABLE public.posts (
   id               SERIAL           PRIMARY KEY,
   title            VARCHAR(255)     NOT NULL,
   description      TEXT             NOT NULL DEFAULT '',
   body             TEXT             NOT NULL,
   tags             TEXT[]           NOT NULL DEFAULT '{}',
+  category_id      INTEGER          REFERENCES public.categories (id) ON DELETE SET NULL,
   thumbnail        TEXT             NOT NULL DEFAULT '',
   thumbnail_blur   TEXT             NOT NULL DEFAULT '/placeholder_image.png',
+  thumbnail_variants JSONB          NOT NULL DEFAULT '[]',
   view_count       INTEGER          NOT NULL DEFAULT 0,
-  like_count       INTEGER          NOT NULL DEFAULT 0,
+  reactions        JSONB            NOT NULL DEFAULT '{}',
+  reaction_total   INTEGER          NOT NULL DEFAULT 0,
+  status           VARCHAR(16)      NOT NULL DEFAULT 'published' CHECK (status IN ('draft', 'published')),
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX posts_category_idx ON public.posts (category_id);
+CREATE INDEX posts_tags_idx ON public.posts USING GIN (tags);
+CREATE INDEX posts_created_idx ON public.posts (created_at DESC, id DESC);
+CREATE INDEX posts_view_count_idx ON public.posts (view_count DESC);
+CREATE INDEX posts_reaction_total_idx ON public.posts (reaction_total DESC, created_at DESC, id DESC);
+
+-- posts.tags 에는 정규화한 slug 만 저장합니다.
+CREATE TABLE public.tags (
+  id               SERIAL           PRIMARY KEY,
+  slug             VARCHAR(50)      NOT NULL UNIQUE,
+  name             VARCHAR(50)      NOT NULL,
+  description      TEXT             NOT NULL DEFAULT '',
+  color            VARCHAR(7)       NOT NULL DEFAULT '',
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE public.series (
+  id               SERIAL           PRIMARY KEY,
+  title            VARCHAR(255)     NOT NULL,
+  slug             VARCHAR(100)     NOT NULL UNIQUE,
+  description      TEXT             NOT NULL DEFAULT '',
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+-- 글은 한 시리즈에만 속합니다. position 은 1 부터 시작하는 편 번호입니다.
+CREATE TABLE public.series_posts (
+  post_id          INTEGER          PRIMARY KEY REFERENCES public.posts (id) ON DELETE CASCADE,
+  series_id        INTEGER          NOT NULL REFERENCES public.series (id) ON DELETE CASCADE,
+  position         INTEGER          NOT NULL,
+  UNIQUE (series_id, position)
+);
+
+CREATE TABLE public.post_reactions (
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  emoji            TEXT             NOT NULL,
+  visitor_id       TEXT             NOT NULL,
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (post_id, visitor_id, emoji)
+);
+
+-- 방문자 중복 판별용 기록. 하루 단위 salt 로 해시하며 날짜가 바뀌면 지웁니다.
+CREATE TABLE public.visitor_salts (
+  day              DATE             PRIMARY KEY,
+  salt             TEXT             NOT NULL
+);
+
+CREATE TABLE public.visitor_hits (
+  scope            TEXT             NOT NULL,
+  post_id          INTEGER          NOT NULL,
+  fingerprint      TEXT             NOT NULL,
+  visitor_id       TEXT             NOT NULL DEFAULT '',
+  day              DATE             NOT NULL,
+  PRIMARY KEY (scope, post_id, fingerprint)
+);
+
+CREATE INDEX visitor_hits_day_idx ON public.visitor_hits (day);
+
+-- visitors 는 그날 처음 본 방문자 수(조회수 증가분), views 는 재조회까지 포함한 수입니다.
+CREATE TABLE public.post_daily_stats (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  views            INTEGER          NOT NULL DEFAULT 0,
+  visitors         INTEGER          NOT NULL DEFAULT 0,
+  reactions        INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id)
+);
+
+CREATE INDEX post_daily_stats_post_idx ON public.post_daily_stats (post_id, day);
+
+-- 읽기 지표는 방문자마다 하루 한 번 보낸 비콘으로 셉니다.
+CREATE TABLE public.post_engagement (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  reads            INTEGER          NOT NULL DEFAULT 0,
+  read_secs        BIGINT           NOT NULL DEFAULT 0,
+  completions      INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id)
+);
+
+-- 유입 경로는 그날 처음 본 방문자 기준으로 셉니다.
+CREATE TABLE public.view_sources (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  source           VARCHAR(16)      NOT NULL,
+  referrer_host    TEXT             NOT NULL DEFAULT '',
+  hits             INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id, source, referrer_host)
+);
+
+CREATE TABLE public.view_campaigns (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  utm_source       TEXT             NOT NULL,
+  utm_medium       TEXT             NOT NULL DEFAULT '',
+  utm_campaign     TEXT             NOT NULL DEFAULT '',
+  hits             INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id, utm_source, utm_medium, utm_campaign)
+);
+
+CREATE TABLE public.bot_hits (
+  day              DATE             NOT NULL,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  reason           TEXT             NOT NULL,
+  hits             INTEGER          NOT NULL DEFAULT 0,
+  PRIMARY KEY (day, post_id, reason)
+);
+
+CREATE TABLE public.media (
+  id               SERIAL           PRIMARY KEY,
+  key              TEXT             NOT NULL UNIQUE,
+  hash             TEXT             NOT NULL,
+  url              TEXT             NOT NULL,
+  filename         TEXT             NOT NULL DEFAULT '',
+  content_type     TEXT             NOT NULL,
+  width            INTEGER          NOT NULL,
+  height           INTEGER          NOT NULL,
+  size             BIGINT           NOT NULL,
+  placeholder      TEXT             NOT NULL DEFAULT '',
+  variants         JSONB            NOT NULL DEFAULT '[]',
   created_at       TIMESTAMP        NOT NULL DEFAULT NOW()
 );
 
+CREATE INDEX media_hash_idx ON public.media (hash);
+
+CREATE TABLE public.media_usages (
+  media_id         INTEGER          NOT NULL REFERENCES public.media (id) ON DELETE CASCADE,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  PRIMARY KEY (media_id, post_id)
+);
+
+CREATE TABLE public.comments (
+  id               SERIAL           PRIMARY KEY,
+  post_id          INTEGER          NOT NULL REFERENCES public.posts (id) ON DELETE CASCADE,
+  parent_id        INTEGER          REFERENCES public.comments (id) ON DELETE CASCADE,
+  nickname         VARCHAR(30)      NOT NULL,
+  password_hash    TEXT             NOT NULL DEFAULT '',
+  body             TEXT             NOT NULL,
+  is_author        BOOLEAN          NOT NULL DEFAULT FALSE,
+  status           VARCHAR(16)      NOT NULL DEFAULT 'approved',
+  deleted          BOOLEAN          NOT NULL DEFAULT FALSE,
+  spam_reasons     TEXT[]           NOT NULL DEFAULT '{}',
+  created_at       TIMESTAMP        NOT NULL DEFAULT NOW(),
+  updated_at       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX comments_post_id_idx ON public.comments (post_id, created_at);
+CREATE INDEX comments_status_idx ON public.comments (status, created_at);
+
 INSERT INTO public.posts (title, description, body, tags, thumbnail, thumbnail_blur) VALUES
 ('첫 번째 게시물', '샘플 설명 1', '샘플 본문 내용 1', '{"rust","actix"}', '/placeholder_image.png', '/placeholder_image.png'),
 ('두 번째 게시물', '샘플 설명 2', '샘플 본문 내용 2', '{"postgres","sql"}', '/placeholder_image.png', '/placeholder_image.png'),
@@ -35,3 +198,13 @@ INSERT INTO public.posts (title, description, body, tags, thumbnail, thumbnail_b
 ('열여덟 번째 게시물', '샘플 설명 18', '샘플 본문 내용 18', '{"sqlx","query"}', '/placeholder_image.png', '/placeholder_image.png'),
 ('열아홉 번째 게시물', '샘플 설명 19', '샘플 본문 내용 19', '{"array","type"}', '/placeholder_image.png', '/placeholder_image.png'),
 ('스무 번째 게시물', '샘플 설명 20', '샘플 본문 내용 20', '{"structure","design"}', '/placeholder_image.png', '/placeholder_image.png');
+
+-- slug 는 tag::service::normalize 와 같은 규칙으로 만듭니다.
+INSERT INTO public.tags (slug, name)
+SELECT DISTINCT ON (slug) slug, left(btrim(tag), 50)
+FROM (
+  SELECT tag, btrim(left(btrim(regexp_replace(lower(btrim(tag)), '[[:space:][:cntrl:]_,/-]+', '-', 'g'), '-'), 50), '-') AS slug
+  FROM public.posts, unnest(tags) AS tag
+) t
+WHERE slug <> ''
+ORDER BY slug, tag;
@@ -0,0 +1,59 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{ DateTime, NaiveDateTime };
+
+use crate::blog::dto::PostSort;
+use crate::blog::model::PostSummary;
+use crate::errors::ServiceError;
+
+/// 무한 스크롤용 위치. 마지막으로 받은 글의 `(created_at, id)` 를 담습니다.
+/// 클라이언트에는 내용을 알 수 없는 문자열로만 내려줍니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostCursor {
+    pub created_at: NaiveDateTime,
+    pub id: i32,
+}
+
+/// 작성 시각 순서일 때만 커서를 쓸 수 있습니다.
+/// 방향을 커서에 같이 넣어 두고, 정렬을 바꿔 보낸 요청은 거절합니다.
+fn direction(sort: PostSort) -> Option<&'static str> {
+    match sort {
+        PostSort::Newest => Some("n"),
+        PostSort::Oldest => Some("o"),
+        _ => None,
+    }
+}
+
+impl PostCursor {
+    pub fn after(post: &PostSummary) -> Self {
+        PostCursor { created_at: post.created_at, id: post.id }
+    }
+
+    pub fn encode(&self, sort: PostSort) -> Option<String> {
+        let direction = direction(sort)?;
+        let raw = format!("{direction}.{}.{}", self.created_at.and_utc().timestamp_micros(), self.id);
+        Some(URL_SAFE_NO_PAD.encode(raw))
+    }
+
+    pub fn decode(cursor: &str, sort: PostSort) -> Result<Self, ServiceError> {
+        let Some(expected) = direction(sort) else {
+            return Err(ServiceError::BadRequest("cursor 는 newest, oldest 정렬에서만 쓸 수 있습니다".into()));
+        };
+        let invalid = || ServiceError::BadRequest("잘못된 cursor 입니다".into());
+
+        let raw = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let mut parts = raw.splitn(3, '.');
+        let (Some(direction), Some(micros), Some(id)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+        if direction != expected {
+            return Err(ServiceError::BadRequest("다른 정렬에서 받은 cursor 입니다".into()));
+        }
+        let micros: i64 = micros.parse().map_err(|_| invalid())?;
+        let id: i32 = id.parse().map_err(|_| invalid())?;
+        let created_at = DateTime::from_timestamp_micros(micros).ok_or_else(invalid)?.naive_utc();
+
+        Ok(PostCursor { created_at, id })
+    }
+}
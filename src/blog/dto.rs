@@ -1,6 +1,14 @@
+use std::str::FromStr;
+
+use actix_web::web;
+use chrono::NaiveDate;
 use serde::{ Deserialize, Serialize };
 
-use crate::blog::model::PostSummary;
+use crate::blog::model::{ Post, PostStatus, PostSummary };
+use crate::category::dto::{ double_option, CategoryRef };
+use crate::errors::ServiceError;
+use crate::series::dto::SeriesNav;
+use crate::stats::referrer::ViewOrigin;
 
 #[derive(Debug, Deserialize)]
 pub struct CreatePost {
@@ -11,12 +19,117 @@ pub struct CreatePost {
     pub tags: Vec<String>,
     pub thumbnail: String,
     pub thumbnail_blur: Option<String>,
+    pub category_id: Option<i32>,
+    #[serde(default)]
+    pub status: PostStatus,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PostListResponse {
     pub total_count: i64,
     pub posts: Vec<PostSummary>,
+    /// 이 다음부터 커서 방식으로 이어 받을 때 씁니다. newest, oldest 정렬에서만 내려줍니다.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// 커서 방식 목록. 마지막 페이지면 `next_cursor` 가 없습니다.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostCursorPage {
+    pub posts: Vec<PostSummary>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PostSort {
+    #[default]
+    Newest,
+    Oldest,
+    Views,
+    /// 반응 수 합계
+    Likes,
+    Title,
+}
+
+impl PostSort {
+    pub fn order_by(&self) -> &'static str {
+        match self {
+            PostSort::Newest => "created_at DESC, id DESC",
+            PostSort::Oldest => "created_at, id",
+            PostSort::Views => "view_count DESC, created_at DESC, id DESC",
+            PostSort::Likes => "reaction_total DESC, created_at DESC, id DESC",
+            PostSort::Title => "title, id",
+        }
+    }
+}
+
+impl FromStr for PostSort {
+    type Err = ServiceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "newest" => Ok(PostSort::Newest),
+            "oldest" => Ok(PostSort::Oldest),
+            "views" => Ok(PostSort::Views),
+            "likes" => Ok(PostSort::Likes),
+            "title" => Ok(PostSort::Title),
+            _ => Err(ServiceError::BadRequest("sort 는 newest, oldest, views, likes, title 중 하나여야 합니다".into())),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TagMatch {
+    /// 모든 태그가 달린 글
+    #[default]
+    All,
+    /// 태그 중 하나라도 달린 글
+    Any,
+}
+
+impl FromStr for TagMatch {
+    type Err = ServiceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(TagMatch::All),
+            "any" => Ok(TagMatch::Any),
+            _ => Err(ServiceError::BadRequest("match 는 all 또는 any 여야 합니다".into())),
+        }
+    }
+}
+
+/// 글 목록 조건. 태그는 정규화하기 전 값이어도 됩니다.
+#[derive(Debug, Default)]
+pub struct PostFilter {
+    pub tags: Vec<String>,
+    pub match_mode: TagMatch,
+    pub exclude: Vec<String>,
+    /// 하위 카테고리의 글까지 포함합니다.
+    pub category: Option<String>,
+    /// 작성일 기준, 양 끝 날짜를 포함합니다.
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub sort: PostSort,
+    /// 기본은 발행된 글입니다. 초안 목록은 관리자만 받을 수 있습니다.
+    pub status: PostStatus,
+}
+
+/// 글 상세. 시리즈에 속한 글이면 시리즈 안에서의 위치를 함께 내려줍니다.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostDetail {
+    #[serde(flatten)]
+    pub post: Post,
+    /// 최상위부터 대표 카테고리까지. 미분류면 비어 있습니다.
+    pub category_path: Vec<CategoryRef>,
+    pub series: Option<SeriesNav>,
+}
+
+/// 작성 시각 기준 이전 글과 다음 글
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostNeighbors {
+    pub previous: Option<PostSummary>,
+    pub next: Option<PostSummary>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,6 +137,11 @@ pub struct UpdatePost {
     pub title: Option<String>,
     pub body: Option<String>,
     pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+    /// `null` 을 보내면 미분류로 돌립니다.
+    #[serde(default, deserialize_with = "double_option")]
+    pub category_id: Option<Option<i32>>,
+    pub status: Option<PostStatus>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,3 +153,90 @@ pub struct BlurRequest {
 pub struct BlurResponse {
     pub data_url: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct ReactionRequest {
+    pub emoji: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReactionSummary {
+    pub emoji: String,
+    pub count: i64,
+    /// 요청한 방문자가 이 반응을 남겼는지 여부
+    pub reacted: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PopularPeriod {
+    Week,
+    Month,
+    #[default]
+    All,
+}
+
+impl PopularPeriod {
+    pub fn days(&self) -> Option<i32> {
+        match self {
+            PopularPeriod::Week => Some(7),
+            PopularPeriod::Month => Some(30),
+            PopularPeriod::All => None,
+        }
+    }
+}
+
+/// 조회 요청에 함께 보내는 유입 정보. 본문 없이 보내도 됩니다.
+#[derive(Debug, Default, Deserialize)]
+pub struct ViewRequest {
+    /// `document.referrer`
+    pub referrer: Option<String>,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+}
+
+impl ViewRequest {
+    pub fn origin(&self, site_hosts: &[String]) -> ViewOrigin {
+        ViewOrigin::parse(
+            self.referrer.as_deref(),
+            self.utm_source.as_deref(),
+            self.utm_medium.as_deref(),
+            self.utm_campaign.as_deref(),
+            site_hosts
+        )
+    }
+}
+
+/// 비콘 하나가 보고할 수 있는 최대 읽은 시간. 탭을 열어둔 채 떠난 경우를 걸러냅니다.
+const MAX_READ_SECS: f64 = 3600.0;
+
+/// 이만큼 스크롤하면 끝까지 읽은 것으로 봅니다.
+const COMPLETION_PERCENT: f64 = 90.0;
+
+/// 읽기 비콘. `navigator.sendBeacon` 은 Content-Type 을 정할 수 없으므로
+/// JSON 과 폼 인코딩(`URLSearchParams`) 본문을 모두 받습니다.
+#[derive(Debug, Deserialize)]
+pub struct EngagementBeacon {
+    pub read_secs: f64,
+    /// 가장 깊이 스크롤한 위치 (0~100)
+    #[serde(default)]
+    pub scroll_percent: f64,
+}
+
+impl EngagementBeacon {
+    pub fn parse(body: &[u8]) -> Option<Self> {
+        serde_json::from_slice(body).ok().or_else(|| {
+            let body = std::str::from_utf8(body).ok()?;
+            web::Query::<Self>::from_query(body.trim()).ok().map(web::Query::into_inner)
+        })
+    }
+
+    pub fn read_secs(&self) -> i64 {
+        if self.read_secs.is_finite() { self.read_secs.clamp(0.0, MAX_READ_SECS).round() as i64 } else { 0 }
+    }
+
+    pub fn completed(&self) -> bool {
+        self.scroll_percent.is_finite() && self.scroll_percent >= COMPLETION_PERCENT
+    }
+}
@@ -1,11 +1,22 @@
 use actix_web::{ get, post, put, delete, web, HttpRequest, HttpResponse, Responder, ResponseError };
 use actix_web::cookie::{ Cookie, time::Duration };
+use chrono::NaiveDate;
+use rand::RngCore;
 use serde::{ Deserialize };
 
-use crate::blog::dto::{ CreatePost, UpdatePost, BlurRequest, BlurResponse };
+use crate::blog::dto::{ CreatePost, PostFilter, UpdatePost, BlurRequest, BlurResponse, EngagementBeacon, PopularPeriod, ReactionRequest, ViewRequest };
+use crate::blog::model::PostStatus;
+use crate::blog::related::RelatedPosts;
 use crate::blog::service;
+use crate::config::AppConfig;
 use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::media::storage::Storage;
+use crate::stats::bot::BotDetector;
+use crate::stats::service as stats_service;
+use crate::stats::views::ViewBuffer;
 use crate::user::handlers::Admin;
+use crate::visitor::VisitorHasher;
 
 #[derive(Debug, Deserialize)]
 struct Pagination {
@@ -13,6 +24,37 @@ struct Pagination {
     #[serde(rename = "pageSize")]
     page_size: Option<u32>,
     tag: Option<String>,
+    /// 쉼표로 구분한 태그 목록
+    tags: Option<String>,
+    #[serde(rename = "match")]
+    match_mode: Option<String>,
+    exclude: Option<String>,
+    category: Option<String>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    sort: Option<String>,
+    /// 주면 `page` 대신 이 위치 다음부터 받습니다.
+    cursor: Option<String>,
+    status: Option<String>,
+}
+
+fn split_list(value: Option<&str>) -> impl Iterator<Item = String> + '_ {
+    value.into_iter().flat_map(|value| value.split(',')).filter(|tag| !tag.trim().is_empty()).map(String::from)
+}
+
+impl Pagination {
+    fn filter(&self) -> Result<PostFilter, ServiceError> {
+        Ok(PostFilter {
+            tags: split_list(self.tag.as_deref()).chain(split_list(self.tags.as_deref())).collect(),
+            match_mode: self.match_mode.as_deref().map(str::parse).transpose()?.unwrap_or_default(),
+            exclude: split_list(self.exclude.as_deref()).collect(),
+            category: self.category.clone(),
+            from: self.from,
+            to: self.to,
+            sort: self.sort.as_deref().map(str::parse).transpose()?.unwrap_or_default(),
+            status: self.status.as_deref().map(str::parse).transpose()?.unwrap_or_default(),
+        })
+    }
 }
 
 #[post("/posts/blur")]
@@ -26,6 +68,7 @@ pub async fn blur_image(web::Json(dto): web::Json<BlurRequest>) -> impl Responde
 #[get("/posts")]
 pub async fn list_posts(
     pool: web::Data<DbPool>,
+    admin: Option<Admin>,
     web::Query(pagination): web::Query<Pagination>
 ) -> impl Responder {
     let page_size = pagination.page_size.unwrap_or(8).max(1) as i64;
@@ -38,93 +81,300 @@ pub async fn list_posts(
         (page_size, 0)
     };
 
-    match service::list_all(&pool, limit, offset, pagination.tag.as_deref()).await {
+    let filter = match pagination.filter() {
+        Ok(filter) => filter,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+    if filter.status == PostStatus::Draft && admin.is_none() {
+        return ServiceError::Unauthorized.error_response();
+    }
+
+    if let Some(cursor) = &pagination.cursor {
+        return match service::list_after(&pool, limit, filter, cursor).await {
+            Ok(data) => { HttpResponse::Ok().json(data) }
+            Err(e) => { e.error_response() }
+        };
+    }
+
+    match service::list_all(&pool, limit, offset, filter).await {
         Ok(data) => { HttpResponse::Ok().json(data) }
         Err(e) => { e.error_response() }
     }
 }
 
 #[get("/posts/{id}")]
-pub async fn get_post(pool: web::Data<DbPool>, path: web::Path<i32>) -> impl Responder {
+pub async fn get_post(
+    pool: web::Data<DbPool>,
+    admin: Option<Admin>,
+    path: web::Path<i32>
+) -> impl Responder {
     let id = path.into_inner();
 
-    match service::get_by_id(&pool, id).await {
+    match service::get_detail(&pool, id).await {
+        Ok(post) if post.post.status == PostStatus::Draft && admin.is_none() => {
+            ServiceError::NotFound.error_response()
+        }
         Ok(post) => { HttpResponse::Ok().json(post) }
         Err(e) => { e.error_response() }
     }
 }
 
+const VIEWED_COOKIE: &str = "viewed_posts";
+/// 쿠키는 빠른 경로일 뿐이라 최근 글만 기억합니다.
+const VIEWED_COOKIE_MAX_IDS: usize = 50;
+
 #[post("/posts/{id}/view")]
-pub async fn view_post(pool: web::Data<DbPool>, path: web::Path<i32>, req: HttpRequest) -> impl Responder {
+#[allow(clippy::too_many_arguments)]
+pub async fn view_post(
+    config: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    hasher: web::Data<VisitorHasher>,
+    bots: web::Data<BotDetector>,
+    views: web::Data<ViewBuffer>,
+    path: web::Path<i32>,
+    body: Option<web::Json<ViewRequest>>,
+    req: HttpRequest
+) -> impl Responder {
     let id = path.into_inner();
 
+    if let Some(reason) = bots.classify(&req) {
+        return match stats_service::record_bot_hit(&pool, id, &reason).await {
+            Ok(_) => { HttpResponse::NoContent().finish() }
+            Err(e) => { e.error_response() }
+        };
+    }
+
     let viewed_ids: Vec<i32> = req
-        .cookie("viewed_posts")
+        .cookie(VIEWED_COOKIE)
         .map(|c| c.value().split(',').filter_map(|s| s.parse().ok()).collect())
         .unwrap_or_default();
 
     if viewed_ids.contains(&id) {
-        return HttpResponse::NoContent().finish();
+        return match service::record_repeat_view(&pool, &views, id).await {
+            Ok(_) => { HttpResponse::NoContent().finish() }
+            Err(e) => { e.error_response() }
+        };
     }
 
-    match service::increment_view(&pool, id).await {
-        Ok(view_count) => {
-            let mut new_ids = viewed_ids;
-            new_ids.push(id);
-            let cookie_value = new_ids.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(",");
-            let cookie = Cookie::build("viewed_posts", cookie_value)
-                .max_age(Duration::days(1))
-                .path("/")
-                .finish();
+    let fingerprint = match hasher.fingerprint(&pool, &req).await {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    let mut new_ids = viewed_ids;
+    new_ids.push(id);
+    let skip = new_ids.len().saturating_sub(VIEWED_COOKIE_MAX_IDS);
+    let cookie_value = new_ids[skip..].iter().map(|n| n.to_string()).collect::<Vec<_>>().join(",");
+    let cookie = Cookie::build(VIEWED_COOKIE, cookie_value)
+        .max_age(Duration::days(1))
+        .path("/")
+        .finish();
+
+    // 설정이 비어 있어도 지금 접속한 호스트에서 온 조회는 내부 이동으로 봅니다.
+    let mut site_hosts = config.stats.site_hosts();
+    let host = req.connection_info().host().to_lowercase();
+    let host = host.split(':').next().unwrap_or_default();
+    site_hosts.push(host.strip_prefix("www.").unwrap_or(host).to_string());
+    let origin = body.map(|b| b.origin(&site_hosts)).unwrap_or_default();
+
+    match service::record_view(&pool, &views, id, &fingerprint, origin).await {
+        Ok(Some(view_count)) => {
             HttpResponse::Ok()
                 .cookie(cookie)
                 .json(serde_json::json!({ "view_count": view_count }))
         }
+        Ok(None) => { HttpResponse::NoContent().cookie(cookie).finish() }
         Err(e) => { e.error_response() }
     }
 }
 
-#[get("/tags")]
-pub async fn list_tags(pool: web::Data<DbPool>) -> impl Responder {
-    match service::get_tags(&pool).await {
-        Ok(tags) => { HttpResponse::Ok().json(tags) }
+#[post("/posts/{id}/engagement")]
+pub async fn record_engagement(
+    pool: web::Data<DbPool>,
+    hasher: web::Data<VisitorHasher>,
+    bots: web::Data<BotDetector>,
+    path: web::Path<i32>,
+    body: web::Bytes,
+    req: HttpRequest
+) -> impl Responder {
+    let id = path.into_inner();
+
+    let Some(beacon) = EngagementBeacon::parse(&body) else {
+        return ServiceError::BadRequest("읽기 정보를 해석할 수 없습니다".into()).error_response();
+    };
+    if bots.classify_agent(&req).is_some() {
+        return HttpResponse::NoContent().finish();
+    }
+
+    let fingerprint = match hasher.fingerprint(&pool, &req).await {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    match service::record_engagement(&pool, id, &fingerprint, &beacon).await {
+        Ok(_) => { HttpResponse::NoContent().finish() }
         Err(e) => { e.error_response() }
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct PopularQuery {
+    #[serde(default)]
+    period: PopularPeriod,
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrendingQuery {
+    limit: Option<u32>,
+}
+
+const MAX_RANKING_LIMIT: u32 = 50;
+
 #[get("/posts/popular")]
-pub async fn popular_posts(pool: web::Data<DbPool>) -> impl Responder {
-    match service::get_popular(&pool).await {
+pub async fn popular_posts(
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<PopularQuery>
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(5).clamp(1, MAX_RANKING_LIMIT) as i64;
+
+    match service::get_popular(&pool, query.period, limit).await {
         Ok(posts) => { HttpResponse::Ok().json(posts) }
         Err(e) => { e.error_response() }
     }
 }
 
-#[post("/posts/{id}/like")]
-pub async fn like_post(pool: web::Data<DbPool>, path: web::Path<i32>, req: HttpRequest) -> impl Responder {
+#[derive(Debug, Deserialize)]
+struct NeighborsQuery {
+    tag: Option<String>,
+    #[serde(default)]
+    series: bool,
+}
+
+#[get("/posts/{id}/neighbors")]
+pub async fn post_neighbors(
+    pool: web::Data<DbPool>,
+    path: web::Path<i32>,
+    web::Query(query): web::Query<NeighborsQuery>
+) -> impl Responder {
     let id = path.into_inner();
 
-    let liked_ids: Vec<i32> = req
-        .cookie("liked_posts")
-        .map(|c| c.value().split(',').filter_map(|s| s.parse().ok()).collect())
-        .unwrap_or_default();
+    match service::get_neighbors(&pool, id, query.tag.as_deref(), query.series).await {
+        Ok(neighbors) => { HttpResponse::Ok().json(neighbors) }
+        Err(e) => { e.error_response() }
+    }
+}
 
-    if liked_ids.contains(&id) {
-        return HttpResponse::NoContent().finish();
+#[derive(Debug, Deserialize)]
+struct RelatedQuery {
+    limit: Option<u32>,
+}
+
+#[get("/posts/{id}/related")]
+pub async fn related_posts(
+    pool: web::Data<DbPool>,
+    related: web::Data<RelatedPosts>,
+    path: web::Path<i32>,
+    web::Query(query): web::Query<RelatedQuery>
+) -> impl Responder {
+    let id = path.into_inner();
+    let limit = query.limit.unwrap_or(4).clamp(1, 12) as usize;
+
+    match service::get_related(&pool, &related, id, limit).await {
+        Ok(posts) => { HttpResponse::Ok().json(posts) }
+        Err(e) => { e.error_response() }
     }
+}
 
-    match service::increment_like(&pool, id).await {
-        Ok(like_count) => {
-            let mut new_ids = liked_ids;
-            new_ids.push(id);
-            let cookie_value = new_ids.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(",");
-            let cookie = Cookie::build("liked_posts", cookie_value)
-                .max_age(Duration::days(7))
-                .path("/")
-                .finish();
-            HttpResponse::Ok()
-                .cookie(cookie)
-                .json(serde_json::json!({ "like_count": like_count }))
+#[get("/posts/trending")]
+pub async fn trending_posts(
+    config: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<TrendingQuery>
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(5).clamp(1, MAX_RANKING_LIMIT) as i64;
+
+    match service::get_trending(&pool, &config.stats, limit).await {
+        Ok(posts) => { HttpResponse::Ok().json(posts) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+const VISITOR_COOKIE: &str = "visitor_id";
+
+/// 반응 토글에 쓰는 방문자 식별자. 쿠키가 없거나 잘못됐으면 새로 발급합니다.
+fn visitor_id(req: &HttpRequest) -> (String, Option<Cookie<'static>>) {
+    if let Some(cookie) = req.cookie(VISITOR_COOKIE) {
+        let value = cookie.value();
+        if value.len() == 32 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+            return (value.to_string(), None);
+        }
+    }
+
+    let mut bytes = [0u8; 16];
+    rand::rng().fill_bytes(&mut bytes);
+    let id = hex::encode(bytes);
+    let cookie = Cookie::build(VISITOR_COOKIE, id.clone())
+        .max_age(Duration::days(365))
+        .path("/")
+        .http_only(true)
+        .finish();
+    (id, Some(cookie))
+}
+
+#[get("/posts/{id}/reactions")]
+pub async fn get_reactions(
+    req: HttpRequest,
+    cfg: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    path: web::Path<i32>
+) -> impl Responder {
+    let id = path.into_inner();
+    let (visitor, new_cookie) = visitor_id(&req);
+
+    match service::get_reactions(&pool, &cfg.reaction.emojis(), id, &visitor).await {
+        Ok(reactions) => {
+            let mut resp = HttpResponse::Ok();
+            if let Some(cookie) = new_cookie {
+                resp.cookie(cookie);
+            }
+            resp.json(reactions)
+        }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/posts/{id}/reactions")]
+pub async fn toggle_reaction(
+    req: HttpRequest,
+    cfg: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    hasher: web::Data<VisitorHasher>,
+    path: web::Path<i32>,
+    web::Json(dto): web::Json<ReactionRequest>
+) -> impl Responder {
+    let id = path.into_inner();
+    let (visitor, new_cookie) = visitor_id(&req);
+    let fingerprint = match hasher.fingerprint(&pool, &req).await {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    match service::toggle_reaction(&pool, &cfg.reaction.emojis(), id, &visitor, &fingerprint, &dto.emoji).await {
+        Ok(reactions) => {
+            let mut resp = HttpResponse::Ok();
+            if let Some(cookie) = new_cookie {
+                resp.cookie(cookie);
+            }
+            resp.json(reactions)
         }
         Err(e) => { e.error_response() }
     }
@@ -134,10 +384,37 @@ pub async fn like_post(pool: web::Data<DbPool>, path: web::Path<i32>, req: HttpR
 pub async fn create_post(
     _: Admin,
     pool: web::Data<DbPool>,
+    storage: web::Data<dyn Storage>,
+    cfg: web::Data<AppConfig>,
+    related: web::Data<RelatedPosts>,
     web::Json(dto): web::Json<CreatePost>
 ) -> impl Responder {
     match service::create(&pool, dto).await {
-        Ok(post) => { HttpResponse::Created().json(post) }
+        Ok(post) => {
+            related.invalidate();
+            let post_id = post.id;
+            actix_web::rt::spawn(async move {
+                if let Err(e) = service::refresh_thumbnail_variants(&pool, storage.get_ref(), &cfg.image, post_id).await {
+                    tracing::warn!("썸네일 변환 이미지 생성 실패 (post {post_id}): {e}");
+                }
+            });
+            HttpResponse::Created().json(post)
+        }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/posts/{id}/thumbnail/variants")]
+pub async fn refresh_thumbnail_variants(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    storage: web::Data<dyn Storage>,
+    cfg: web::Data<AppConfig>,
+    path: web::Path<i32>
+) -> impl Responder {
+    let id = path.into_inner();
+    match service::refresh_thumbnail_variants(&pool, storage.get_ref(), &cfg.image, id).await {
+        Ok(post) => { HttpResponse::Ok().json(post) }
         Err(e) => { e.error_response() }
     }
 }
@@ -146,12 +423,16 @@ pub async fn create_post(
 pub async fn update_post(
     _: Admin,
     pool: web::Data<DbPool>,
+    related: web::Data<RelatedPosts>,
     path: web::Path<i32>,
     web::Json(dto): web::Json<UpdatePost>
 ) -> impl Responder {
     let id = path.into_inner();
     match service::update(&pool, id, dto).await {
-        Ok(post) => { HttpResponse::Ok().json(post) }
+        Ok(post) => {
+            related.invalidate();
+            HttpResponse::Ok().json(post)
+        }
         Err(e) => { e.error_response() }
     }
 }
@@ -160,11 +441,15 @@ pub async fn update_post(
 pub async fn delete_post(
     _: Admin,
     pool: web::Data<DbPool>,
+    related: web::Data<RelatedPosts>,
     path: web::Path<i32>
 ) -> impl Responder {
     let id = path.into_inner();
     match service::delete(&pool, id).await {
-        Ok(_) => { HttpResponse::NoContent().finish() }
+        Ok(_) => {
+            related.invalidate();
+            HttpResponse::NoContent().finish()
+        }
         Err(e) => { e.error_response() }
     }
 }
@@ -1,5 +1,7 @@
 pub mod model;
 pub mod dto;
+pub mod cursor;
+pub mod related;
 pub mod service;
 pub mod handlers;
 pub mod routes;
@@ -1,6 +1,14 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+
 use serde::{ Serialize, Deserialize };
 use chrono::NaiveDateTime;
 use tokio_pg_mapper_derive::PostgresMapper;
+use tokio_postgres::types::{ FromSql, Json, Type };
+
+use crate::errors::ServiceError;
+use crate::media::model::ImageVariants;
 
 #[derive(Debug, Serialize, Deserialize, PostgresMapper)]
 #[pg_mapper(table = "posts")]
@@ -10,10 +18,14 @@ pub struct Post {
     pub description: String,
     pub body: String,
     pub tags: Vec<String>,
+    /// 대표 카테고리
+    pub category_id: Option<i32>,
     pub thumbnail: String,
     pub thumbnail_blur: String,
+    pub thumbnail_variants: ImageVariants,
     pub view_count: i32,
-    pub like_count: i32,
+    pub reactions: ReactionCounts,
+    pub status: PostStatus,
     pub created_at: NaiveDateTime,
 }
 
@@ -26,7 +38,67 @@ pub struct PostSummary {
     pub tags: Vec<String>,
     pub thumbnail: String,
     pub thumbnail_blur: String,
+    pub thumbnail_variants: ImageVariants,
     pub view_count: i32,
-    pub like_count: i32,
+    pub reactions: ReactionCounts,
     pub created_at: NaiveDateTime,
 }
+
+/// 초안은 관리자만 볼 수 있고 목록, 이웃 글, 인기 글 등에는 나오지 않습니다.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    Draft,
+    #[default]
+    Published,
+}
+
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Published => "published",
+        }
+    }
+}
+
+impl FromStr for PostStatus {
+    type Err = ServiceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(PostStatus::Draft),
+            "published" => Ok(PostStatus::Published),
+            _ => Err(ServiceError::BadRequest("status 는 draft 또는 published 여야 합니다".into())),
+        }
+    }
+}
+
+impl<'a> FromSql<'a> for PostStatus {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match <&str as FromSql>::from_sql(ty, raw)? {
+            "draft" => Ok(PostStatus::Draft),
+            "published" => Ok(PostStatus::Published),
+            other => Err(format!("unknown post status: {}", other).into()),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+/// 이모지별 반응 수. DB 에는 JSONB 객체로 저장됩니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReactionCounts(pub BTreeMap<String, i64>);
+
+impl<'a> FromSql<'a> for ReactionCounts {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Json::<BTreeMap<String, i64>>::from_sql(ty, raw).map(|Json(counts)| ReactionCounts(counts))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Json<BTreeMap<String, i64>> as FromSql>::accepts(ty)
+    }
+}
@@ -0,0 +1,172 @@
+use std::cmp::Ordering;
+use std::collections::{ HashMap, HashSet };
+use std::sync::{ Arc, Mutex };
+
+use chrono::NaiveDateTime;
+
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+
+/// 태그가 겹치는 정도와 제목·설명의 TF-IDF 유사도를 섞는 비율
+const TAG_WEIGHT: f64 = 0.6;
+const TEXT_WEIGHT: f64 = 0.4;
+
+/// 이보다 짧은 단어는 조사나 기호일 때가 많아 버립니다.
+const MIN_TOKEN_CHARS: usize = 2;
+
+struct Document {
+    id: i32,
+    created_at: NaiveDateTime,
+    tags: HashSet<String>,
+    /// 길이를 1 로 맞춘 TF-IDF 벡터
+    terms: HashMap<String, f64>,
+}
+
+struct Index {
+    documents: Vec<Document>,
+    tag_idf: HashMap<String, f64>,
+    /// 글마다 계산해 둔 순위
+    ranked: Mutex<HashMap<i32, Arc<Vec<i32>>>>,
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|t| t.chars().count() >= MIN_TOKEN_CHARS)
+        .collect()
+}
+
+/// 모든 글에 나오는 단어는 0 이 되어 유사도에 영향을 주지 않습니다.
+fn idf(total: usize, df: usize) -> f64 {
+    (total as f64 / df as f64).ln()
+}
+
+impl Index {
+    fn build(rows: Vec<(i32, String, String, Vec<String>, NaiveDateTime)>) -> Index {
+        let total = rows.len();
+
+        let mut term_df: HashMap<String, usize> = HashMap::new();
+        let mut tag_df: HashMap<String, usize> = HashMap::new();
+        let parsed: Vec<_> = rows
+            .into_iter()
+            .map(|(id, title, description, tags, created_at)| {
+                let tokens = tokenize(&format!("{title} {description}"));
+                for term in tokens.iter().collect::<HashSet<_>>() {
+                    *term_df.entry(term.clone()).or_default() += 1;
+                }
+                let tags: HashSet<String> = tags.into_iter().map(|t| t.to_lowercase()).collect();
+                for tag in &tags {
+                    *tag_df.entry(tag.clone()).or_default() += 1;
+                }
+                (id, created_at, tags, tokens)
+            })
+            .collect();
+
+        let documents = parsed
+            .into_iter()
+            .map(|(id, created_at, tags, tokens)| {
+                let mut terms: HashMap<String, f64> = HashMap::new();
+                for token in tokens {
+                    *terms.entry(token).or_default() += 1.0;
+                }
+                for (term, weight) in terms.iter_mut() {
+                    *weight *= idf(total, term_df[term]);
+                }
+                let norm = terms.values().map(|w| w * w).sum::<f64>().sqrt();
+                terms.retain(|_, w| *w > 0.0);
+                for weight in terms.values_mut() {
+                    *weight /= norm;
+                }
+                Document { id, created_at, tags, terms }
+            })
+            .collect();
+
+        let tag_idf = tag_df
+            .into_iter()
+            .map(|(tag, df)| (tag, idf(total, df).max(0.0) + 1.0))
+            .collect();
+
+        Index { documents, tag_idf, ranked: Mutex::new(HashMap::new()) }
+    }
+
+    /// 원본 글의 태그 가중치 중 겹치는 태그가 차지하는 비율
+    fn tag_score(&self, source: &Document, other: &Document) -> f64 {
+        let total: f64 = source.tags.iter().map(|t| self.tag_idf[t]).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        let shared: f64 = source.tags.intersection(&other.tags).map(|t| self.tag_idf[t]).sum();
+        shared / total
+    }
+
+    fn text_score(source: &Document, other: &Document) -> f64 {
+        source.terms
+            .iter()
+            .filter_map(|(term, w)| other.terms.get(term).map(|o| w * o))
+            .sum()
+    }
+
+    fn rank(&self, post_id: i32) -> Option<Arc<Vec<i32>>> {
+        if let Some(ranked) = self.ranked.lock().unwrap().get(&post_id) {
+            return Some(ranked.clone());
+        }
+
+        let source = self.documents.iter().find(|d| d.id == post_id)?;
+        let mut scored: Vec<(f64, &Document)> = self.documents
+            .iter()
+            .filter(|d| d.id != post_id)
+            .map(|d| (TAG_WEIGHT * self.tag_score(source, d) + TEXT_WEIGHT * Self::text_score(source, d), d))
+            .filter(|(score, _)| *score > 0.0)
+            .collect();
+        scored.sort_by(|(a, da), (b, db)| {
+            b.partial_cmp(a)
+                .unwrap_or(Ordering::Equal)
+                .then(db.created_at.cmp(&da.created_at))
+                .then(db.id.cmp(&da.id))
+        });
+
+        let ranked = Arc::new(scored.into_iter().map(|(_, d)| d.id).collect::<Vec<_>>());
+        self.ranked.lock().unwrap().insert(post_id, ranked.clone());
+        Some(ranked)
+    }
+}
+
+/// 관련 글 순위를 메모리에 들고 있다가 글이 바뀌면 버립니다.
+#[derive(Default)]
+pub struct RelatedPosts {
+    index: Mutex<Option<Arc<Index>>>,
+}
+
+impl RelatedPosts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 글을 쓰거나 고치거나 지운 뒤에 불러야 합니다.
+    pub fn invalidate(&self) {
+        *self.index.lock().unwrap() = None;
+    }
+
+    async fn index(&self, pool: &DbPool) -> Result<Arc<Index>, ServiceError> {
+        if let Some(index) = self.index.lock().unwrap().as_ref() {
+            return Ok(index.clone());
+        }
+
+        let client = pool.get().await?;
+        let rows = client
+            .query("SELECT id, title, description, tags, created_at FROM posts WHERE status = 'published'", &[]).await?
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2), row.get(3), row.get(4)))
+            .collect();
+
+        let index = Arc::new(Index::build(rows));
+        *self.index.lock().unwrap() = Some(index.clone());
+        Ok(index)
+    }
+
+    /// 관련도 순으로 정렬한 글 ID. 글이 없으면 `NotFound` 를 돌려줍니다.
+    pub async fn related_ids(&self, pool: &DbPool, post_id: i32) -> Result<Arc<Vec<i32>>, ServiceError> {
+        let index = self.index(pool).await?;
+        index.rank(post_id).ok_or(ServiceError::NotFound)
+    }
+}
@@ -2,14 +2,19 @@ use actix_web::web;
 use crate::blog::handlers;
 
 pub fn init(cfg: &mut web::ServiceConfig) {
-    cfg.service(handlers::list_tags)
-        .service(handlers::popular_posts)
+    cfg.service(handlers::popular_posts)
+        .service(handlers::trending_posts)
         .service(handlers::list_posts)
         .service(handlers::blur_image)
         .service(handlers::get_post)
+        .service(handlers::related_posts)
+        .service(handlers::post_neighbors)
         .service(handlers::view_post)
-        .service(handlers::like_post)
+        .service(handlers::record_engagement)
+        .service(handlers::get_reactions)
+        .service(handlers::toggle_reaction)
         .service(handlers::create_post)
+        .service(handlers::refresh_thumbnail_variants)
         .service(handlers::update_post)
         .service(handlers::delete_post);
 }
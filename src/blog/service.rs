@@ -1,95 +1,243 @@
+use chrono::{ Days, NaiveDateTime, NaiveTime };
+use deadpool_postgres::GenericClient;
 use tokio_pg_mapper::FromTokioPostgresRow;
-use reqwest::Client;
-use image::{ imageops::blur, DynamicImage };
-use image::codecs::jpeg::JpegEncoder;
-use base64::engine::general_purpose::STANDARD;
-use base64::Engine;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::types::{ Json, ToSql };
+use image::DynamicImage;
 
 use crate::db::DbPool;
-use crate::blog::model::{ Post, PostSummary };
-use crate::blog::dto::{ CreatePost, UpdatePost, PostListResponse };
+use crate::blog::cursor::PostCursor;
+use crate::blog::related::RelatedPosts;
+use crate::blog::model::{ Post, PostSummary, ReactionCounts };
+use crate::blog::dto::{ CreatePost, PostCursorPage, PostDetail, PostFilter, PostNeighbors, PostSort, TagMatch, UpdatePost, EngagementBeacon, PostListResponse, PopularPeriod, ReactionSummary };
 use crate::errors::ServiceError;
+use crate::config::{ ImageConfig, StatsConfig };
+use crate::media::service::{ self as media_service, blur_data_url, fetch_remote };
+use crate::media::storage::Storage;
+use crate::category::service as category_service;
+use crate::series::service as series_service;
+use crate::stats::referrer::ViewOrigin;
+use crate::stats::service as stats_service;
+use crate::stats::views::ViewBuffer;
+use crate::tag::service as tag_service;
+use crate::visitor::{ self, Fingerprint };
 
 pub async fn blur_image(url: &str) -> Result<String, ServiceError> {
-    let response = Client::new()
-        .get(url)
-        .send().await
-        .map_err(|e| ServiceError::BadRequest(format!("이미지 다운로드 실패: {}", e)))?;
-    let bytes = response
-        .bytes().await
-        .map_err(|e| ServiceError::BadRequest(format!("이미지 바이트 읽기 실패: {}", e)))?;
+    let bytes = fetch_remote(url).await?;
 
     let img: DynamicImage = image
         ::load_from_memory(&bytes)
         .map_err(|e| ServiceError::InternalServerError(e.to_string()))?;
-    let blurred = blur(&img, 10.0);
+    blur_data_url(&img)
+}
+
+const MAX_FILTER_TAGS: usize = 10;
 
-    let mut buf = Vec::new();
-    let mut encoder = JpegEncoder::new_with_quality(&mut buf, 60);
-    encoder.encode_image(&blurred).map_err(|e| ServiceError::InternalServerError(e.to_string()))?;
+fn normalize_tags(tags: &[String], what: &str) -> Result<Vec<String>, ServiceError> {
+    if tags.len() > MAX_FILTER_TAGS {
+        return Err(ServiceError::BadRequest(format!("{what} 태그는 {}개까지 지정할 수 있습니다", MAX_FILTER_TAGS)));
+    }
+    let mut slugs: Vec<String> = Vec::new();
+    for tag in tags {
+        let slug = tag_service::normalize(tag)?;
+        if !slugs.contains(&slug) {
+            slugs.push(slug);
+        }
+    }
+    Ok(slugs)
+}
 
-    let b64 = STANDARD.encode(&buf);
-    Ok(format!("data:image/jpeg;base64,{}", b64))
+/// 정규화하고 검증을 마친 목록 조건
+struct PostQuery {
+    tags: Vec<String>,
+    match_mode: TagMatch,
+    exclude: Vec<String>,
+    category_ids: Option<Vec<i32>>,
+    from: Option<NaiveDateTime>,
+    until: Option<NaiveDateTime>,
+    sort: PostSort,
+    status: &'static str,
 }
 
+impl PostQuery {
+    async fn new(client: &impl GenericClient, filter: PostFilter) -> Result<Self, ServiceError> {
+        let tags = normalize_tags(&filter.tags, "검색할")?;
+        let exclude = normalize_tags(&filter.exclude, "제외할")?;
+        if tags.iter().any(|tag| exclude.contains(tag)) {
+            return Err(ServiceError::BadRequest("같은 태그를 포함하면서 제외할 수 없습니다".into()));
+        }
+        if let (Some(from), Some(to)) = (filter.from, filter.to)
+            && from > to
+        {
+            return Err(ServiceError::BadRequest("시작일이 종료일보다 늦습니다".into()));
+        }
+
+        // 카테고리로 거르면 하위 카테고리의 글까지 포함합니다.
+        let category_ids = match &filter.category {
+            Some(slug) => Some(category_service::subtree_ids(client, slug).await?),
+            None => None,
+        };
+
+        Ok(PostQuery {
+            tags,
+            match_mode: filter.match_mode,
+            exclude,
+            category_ids,
+            from: filter.from.map(|day| day.and_time(NaiveTime::MIN)),
+            until: filter.to.map(|day| (day + Days::new(1)).and_time(NaiveTime::MIN)),
+            sort: filter.sort,
+            status: filter.status.as_str(),
+        })
+    }
+
+    /// 쓰인 조건만 WHERE 에 넣어야 tags 의 GIN 인덱스를 탈 수 있습니다.
+    fn clauses<'a>(&'a self, params: &mut Vec<&'a (dyn ToSql + Sync)>) -> Vec<String> {
+        params.push(&self.status);
+        let mut clauses = vec![format!("status = ${}", params.len())];
+        if !self.tags.is_empty() {
+            params.push(&self.tags);
+            let op = match self.match_mode {
+                TagMatch::All => "@>",
+                TagMatch::Any => "&&",
+            };
+            clauses.push(format!("tags {op} ${}", params.len()));
+        }
+        if !self.exclude.is_empty() {
+            params.push(&self.exclude);
+            clauses.push(format!("NOT tags && ${}", params.len()));
+        }
+        if let Some(category_ids) = &self.category_ids {
+            params.push(category_ids);
+            clauses.push(format!("category_id = ANY(${})", params.len()));
+        }
+        if let Some(from) = &self.from {
+            params.push(from);
+            clauses.push(format!("created_at >= ${}", params.len()));
+        }
+        if let Some(until) = &self.until {
+            params.push(until);
+            clauses.push(format!("created_at < ${}", params.len()));
+        }
+        clauses
+    }
+}
+
+fn where_sql(clauses: &[String]) -> String {
+    if clauses.is_empty() { String::new() } else { format!("WHERE {}", clauses.join(" AND ")) }
+}
+
+/// 페이지 번호 방식. 마지막 페이지가 아니면 이어서 받을 `next_cursor` 도 함께 내려줍니다.
 pub async fn list_all(
     pool: &DbPool,
     limit: i64,
     offset: i64,
-    tag: Option<&str>,
+    filter: PostFilter,
 ) -> Result<PostListResponse, ServiceError> {
     let client = pool.get().await?;
+    let query = PostQuery::new(&client, filter).await?;
 
-    let (total_count, rows) = if let Some(tag) = tag {
-        let count_row = client
-            .query_one("SELECT COUNT(*) FROM posts WHERE $1 = ANY(tags)", &[&tag]).await?;
-        let total_count: i64 = count_row.get(0);
+    let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+    let where_clause = where_sql(&query.clauses(&mut params));
 
-        let stmt = client
-            .prepare_cached(
-                "SELECT id, title, description, tags, thumbnail, thumbnail_blur, view_count, like_count, created_at
-                 FROM posts
-                 WHERE $1 = ANY(tags)
-                 ORDER BY created_at DESC, id DESC
-                 OFFSET $2
-                 LIMIT  $3"
-            ).await?;
-        let rows = client.query(&stmt, &[&tag, &offset, &limit]).await?;
-        (total_count, rows)
-    } else {
-        let count_row = client
-            .query_one("SELECT COUNT(*) FROM posts", &[]).await?;
-        let total_count: i64 = count_row.get(0);
+    let count_row = client
+        .query_one(&format!("SELECT COUNT(*) FROM posts {where_clause}"), &params).await?;
+    let total_count: i64 = count_row.get(0);
 
-        let stmt = client
-            .prepare_cached(
-                "SELECT id, title, description, tags, thumbnail, thumbnail_blur, view_count, like_count, created_at
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "SELECT id, title, description, tags, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, created_at
                  FROM posts
-                 ORDER BY created_at DESC, id DESC
-                 OFFSET $1
-                 LIMIT  $2"
-            ).await?;
-        let rows = client.query(&stmt, &[&offset, &limit]).await?;
-        (total_count, rows)
-    };
+                 {where_clause}
+                 ORDER BY {}
+                 OFFSET ${}
+                 LIMIT  ${}",
+                query.sort.order_by(),
+                params.len() + 1,
+                params.len() + 2
+            )
+        ).await?;
+    params.push(&offset);
+    params.push(&limit);
+    let rows = client.query(&stmt, &params).await?;
 
     let posts = rows
         .into_iter()
         .map(|row| PostSummary::from_row_ref(&row).map_err(ServiceError::from))
         .collect::<Result<Vec<_>, _>>()?;
 
+    let next_cursor = match posts.last() {
+        Some(last) if offset + (posts.len() as i64) < total_count => PostCursor::after(last).encode(query.sort),
+        _ => None,
+    };
+
     Ok(PostListResponse {
         total_count,
         posts,
+        next_cursor,
     })
 }
 
+/// 커서 방식. 전체 개수를 세지 않고, 한 건 더 읽어 다음 페이지가 있는지만 봅니다.
+pub async fn list_after(
+    pool: &DbPool,
+    limit: i64,
+    filter: PostFilter,
+    cursor: &str,
+) -> Result<PostCursorPage, ServiceError> {
+    let cursor = PostCursor::decode(cursor, filter.sort)?;
+
+    let client = pool.get().await?;
+    let query = PostQuery::new(&client, filter).await?;
+
+    let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+    let mut clauses = query.clauses(&mut params);
+    let op = match query.sort {
+        PostSort::Oldest => ">",
+        _ => "<",
+    };
+    params.push(&cursor.created_at);
+    params.push(&cursor.id);
+    clauses.push(format!("(created_at, id) {op} (${}, ${})", params.len() - 1, params.len()));
+
+    let fetch = limit + 1;
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "SELECT id, title, description, tags, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, created_at
+                 FROM posts
+                 {}
+                 ORDER BY {}
+                 LIMIT ${}",
+                where_sql(&clauses),
+                query.sort.order_by(),
+                params.len() + 1
+            )
+        ).await?;
+    params.push(&fetch);
+    let rows = client.query(&stmt, &params).await?;
+
+    let mut posts = rows
+        .into_iter()
+        .map(|row| PostSummary::from_row_ref(&row).map_err(ServiceError::from))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let next_cursor = if posts.len() as i64 > limit {
+        posts.truncate(limit as usize);
+        posts.last().and_then(|last| PostCursor::after(last).encode(query.sort))
+    } else {
+        None
+    };
+
+    Ok(PostCursorPage { posts, next_cursor })
+}
+
 pub async fn get_by_id(pool: &DbPool, post_id: i32) -> Result<Post, ServiceError> {
     let client = pool.get().await?;
 
     let stmt = client
         .prepare_cached(
-            "SELECT id, title, description, body, tags, thumbnail, thumbnail_blur, view_count, like_count, created_at
+            "SELECT id, title, description, body, tags, category_id, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, status, created_at
              FROM posts WHERE id = $1"
         ).await?;
 
@@ -98,45 +246,232 @@ pub async fn get_by_id(pool: &DbPool, post_id: i32) -> Result<Post, ServiceError
     Ok(Post::from_row_ref(&row)?)
 }
 
-pub async fn increment_view(pool: &DbPool, post_id: i32) -> Result<i32, ServiceError> {
+pub async fn get_detail(pool: &DbPool, post_id: i32) -> Result<PostDetail, ServiceError> {
+    let post = get_by_id(pool, post_id).await?;
     let client = pool.get().await?;
+    let category_path = match post.category_id {
+        Some(category_id) => category_service::path_for(&client, category_id).await?,
+        None => Vec::new(),
+    };
+    let series = series_service::nav_for_post(&client, post_id).await?;
 
-    let stmt = client
+    Ok(PostDetail { post, category_path, series })
+}
+
+/// 방문자가 남기는 기록(조회, 읽기, 반응, 댓글)은 발행된 글에만 받습니다.
+pub async fn ensure_published(client: &impl GenericClient, post_id: i32) -> Result<(), ServiceError> {
+    client
+        .query_opt("SELECT 1 FROM posts WHERE id = $1 AND status = 'published'", &[&post_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+    Ok(())
+}
+
+/// 오늘 이미 본 방문자면 조회수를 올리지 않고 `None` 을 돌려줍니다.
+/// 버퍼 모드에서는 근사치를 바로 돌려주고 실제 반영은 주기 작업이 합니다.
+pub async fn record_view(
+    pool: &DbPool,
+    views: &ViewBuffer,
+    post_id: i32,
+    fingerprint: &Fingerprint,
+    origin: ViewOrigin
+) -> Result<Option<i32>, ServiceError> {
+    if views.is_buffered() {
+        let client = pool.get().await?;
+        ensure_published(&client, post_id).await?;
+        if visitor::record_hit(&client, "view", post_id, fingerprint, "").await?.is_some() {
+            views.add_repeat(post_id);
+            return Ok(None);
+        }
+        drop(client);
+        return Ok(Some(views.add(pool, post_id, origin).await?));
+    }
+
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+    ensure_published(&tx, post_id).await?;
+
+    if visitor::record_hit(&tx, "view", post_id, fingerprint, "").await?.is_some() {
+        stats_service::record_daily(&tx, post_id, 1, 0, 0).await?;
+        tx.commit().await?;
+        return Ok(None);
+    }
+
+    let stmt = tx
         .prepare_cached(
             "UPDATE posts SET view_count = view_count + 1 WHERE id = $1
              RETURNING view_count"
         ).await?;
 
-    let row = client.query_one(&stmt, &[&post_id]).await.map_err(|_| ServiceError::NotFound)?;
+    let row = tx.query_one(&stmt, &[&post_id]).await?;
+    stats_service::record_daily(&tx, post_id, 1, 1, 0).await?;
+    stats_service::record_origins(&tx, fingerprint.day, &[(post_id, origin, 1)]).await?;
+    tx.commit().await?;
 
-    Ok(row.get(0))
+    Ok(Some(row.get(0)))
 }
 
-pub async fn get_tags(pool: &DbPool) -> Result<Vec<String>, ServiceError> {
+/// 방문자마다 하루 첫 비콘만 셉니다. 이미 받았으면 `false` 를 돌려줍니다.
+pub async fn record_engagement(
+    pool: &DbPool,
+    post_id: i32,
+    fingerprint: &Fingerprint,
+    beacon: &EngagementBeacon
+) -> Result<bool, ServiceError> {
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+    ensure_published(&tx, post_id).await?;
+
+    if visitor::record_hit(&tx, "engagement", post_id, fingerprint, "").await?.is_some() {
+        return Ok(false);
+    }
+
+    stats_service::record_engagement(&tx, fingerprint.day, post_id, beacon.read_secs(), beacon.completed()).await?;
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// 쿠키로 이미 본 글임을 알 수 있는 재조회. 일별 통계에만 더합니다.
+pub async fn record_repeat_view(pool: &DbPool, views: &ViewBuffer, post_id: i32) -> Result<(), ServiceError> {
     let client = pool.get().await?;
+    ensure_published(&client, post_id).await?;
 
-    let stmt = client
+    if views.is_buffered() {
+        views.add_repeat(post_id);
+        return Ok(());
+    }
+
+    stats_service::record_daily(&client, post_id, 1, 0, 0).await
+}
+
+/// `tag` 를 주면 그 태그가 달린 글 사이에서만 찾습니다.
+/// `in_series` 면 작성 시각 대신 같은 시리즈의 편 순서를 따릅니다.
+pub async fn get_neighbors(
+    pool: &DbPool,
+    post_id: i32,
+    tag: Option<&str>,
+    in_series: bool
+) -> Result<PostNeighbors, ServiceError> {
+    let tag = tag.map(tag_service::normalize).transpose()?;
+    let client = pool.get().await?;
+
+    let row = client
+        .query_opt("SELECT created_at FROM posts WHERE id = $1", &[&post_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+    let created_at: NaiveDateTime = row.get(0);
+
+    if in_series {
+        let stmt = client
+            .prepare_cached(
+                "SELECT p.id, p.title, p.description, p.tags, p.thumbnail, p.thumbnail_blur, p.thumbnail_variants,
+                        p.view_count, p.reactions, p.created_at, sp.position > me.position AS is_next
+                 FROM series_posts me
+                 JOIN series_posts sp ON sp.series_id = me.series_id AND sp.position IN (me.position - 1, me.position + 1)
+                 JOIN posts p ON p.id = sp.post_id
+                 WHERE me.post_id = $1 AND p.status = 'published' AND ($2::text IS NULL OR $2 = ANY(p.tags))"
+            ).await?;
+        let mut neighbors = PostNeighbors { previous: None, next: None };
+        for row in client.query(&stmt, &[&post_id, &tag]).await? {
+            let post = PostSummary::from_row_ref(&row)?;
+            if row.get("is_next") {
+                neighbors.next = Some(post);
+            } else {
+                neighbors.previous = Some(post);
+            }
+        }
+        return Ok(neighbors);
+    }
+
+    let previous = client
+        .prepare_cached(
+            "SELECT id, title, description, tags, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, created_at
+             FROM posts
+             WHERE (created_at, id) < ($1, $2) AND status = 'published' AND ($3::text IS NULL OR $3 = ANY(tags))
+             ORDER BY created_at DESC, id DESC
+             LIMIT 1"
+        ).await?;
+    let next = client
         .prepare_cached(
-            "SELECT DISTINCT unnest(tags) AS tag FROM posts ORDER BY tag"
+            "SELECT id, title, description, tags, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, created_at
+             FROM posts
+             WHERE (created_at, id) > ($1, $2) AND status = 'published' AND ($3::text IS NULL OR $3 = ANY(tags))
+             ORDER BY created_at, id
+             LIMIT 1"
         ).await?;
 
-    let rows = client.query(&stmt, &[]).await?;
+    let previous = client.query_opt(&previous, &[&created_at, &post_id, &tag]).await?;
+    let next = client.query_opt(&next, &[&created_at, &post_id, &tag]).await?;
 
-    Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    Ok(PostNeighbors {
+        previous: previous.map(|row| PostSummary::from_row_ref(&row)).transpose()?,
+        next: next.map(|row| PostSummary::from_row_ref(&row)).transpose()?,
+    })
 }
 
-pub async fn get_popular(pool: &DbPool) -> Result<Vec<PostSummary>, ServiceError> {
-    let client = pool.get().await?;
+pub async fn get_related(
+    pool: &DbPool,
+    related: &RelatedPosts,
+    post_id: i32,
+    limit: usize
+) -> Result<Vec<PostSummary>, ServiceError> {
+    let ranked = related.related_ids(pool, post_id).await?;
+    let ids: Vec<i32> = ranked.iter().take(limit).copied().collect();
 
+    let client = pool.get().await?;
     let stmt = client
         .prepare_cached(
-            "SELECT id, title, description, tags, thumbnail, thumbnail_blur, view_count, like_count, created_at
+            "SELECT id, title, description, tags, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, created_at
              FROM posts
-             ORDER BY like_count DESC, view_count DESC, created_at DESC
-             LIMIT 5"
+             WHERE id = ANY($1) AND status = 'published'"
         ).await?;
+    let rows = client.query(&stmt, &[&ids]).await?;
 
-    let rows = client.query(&stmt, &[]).await?;
+    let mut posts = rows
+        .into_iter()
+        .map(|row| PostSummary::from_row_ref(&row).map_err(ServiceError::from))
+        .collect::<Result<Vec<_>, _>>()?;
+    posts.sort_by_key(|post| ids.iter().position(|id| *id == post.id));
+
+    Ok(posts)
+}
+
+/// 반응 하나를 방문 몇 번으로 칠지
+const REACTION_WEIGHT: i32 = 5;
+
+pub async fn get_popular(pool: &DbPool, period: PopularPeriod, limit: i64) -> Result<Vec<PostSummary>, ServiceError> {
+    let client = pool.get().await?;
+
+    let rows = match period.days() {
+        None => {
+            let stmt = client
+                .prepare_cached(
+                    "SELECT id, title, description, tags, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, created_at
+                     FROM posts
+                     WHERE status = 'published'
+                     ORDER BY reaction_total DESC, view_count DESC, created_at DESC
+                     LIMIT $1"
+                ).await?;
+            client.query(&stmt, &[&limit]).await?
+        }
+        Some(days) => {
+            let stmt = client
+                .prepare_cached(
+                    "SELECT p.id, p.title, p.description, p.tags, p.thumbnail, p.thumbnail_blur, p.thumbnail_variants,
+                            p.view_count, p.reactions, p.created_at
+                     FROM posts p
+                     JOIN (
+                         SELECT post_id, SUM(visitors + reactions * $2) AS score
+                         FROM post_daily_stats
+                         WHERE day > CURRENT_DATE - $1::int
+                         GROUP BY post_id
+                     ) s ON s.post_id = p.id
+                     WHERE s.score > 0 AND p.status = 'published'
+                     ORDER BY s.score DESC, p.created_at DESC
+                     LIMIT $3"
+                ).await?;
+            client.query(&stmt, &[&days, &REACTION_WEIGHT, &limit]).await?
+        }
+    };
 
     let posts = rows
         .into_iter()
@@ -146,18 +481,144 @@ pub async fn get_popular(pool: &DbPool) -> Result<Vec<PostSummary>, ServiceError
     Ok(posts)
 }
 
-pub async fn increment_like(pool: &DbPool, post_id: i32) -> Result<i32, ServiceError> {
+/// 최근 방문과 반응을 날짜가 지날수록 반감기에 맞춰 줄여 더한 점수로 정렬합니다.
+pub async fn get_trending(pool: &DbPool, cfg: &StatsConfig, limit: i64) -> Result<Vec<PostSummary>, ServiceError> {
     let client = pool.get().await?;
 
+    let window_days = cfg.trending_window_days.max(1) as i32;
+    let half_life_hours = f64::from(cfg.trending_half_life_hours.max(1));
+
     let stmt = client
         .prepare_cached(
-            "UPDATE posts SET like_count = like_count + 1 WHERE id = $1
-             RETURNING like_count"
+            "SELECT p.id, p.title, p.description, p.tags, p.thumbnail, p.thumbnail_blur, p.thumbnail_variants,
+                    p.view_count, p.reactions, p.created_at
+             FROM posts p
+             JOIN (
+                 SELECT post_id,
+                        SUM((visitors + reactions * $3) * power(0.5, (CURRENT_DATE - day) * 24 / $2::float8)) AS score
+                 FROM post_daily_stats
+                 WHERE day > CURRENT_DATE - $1::int
+                 GROUP BY post_id
+             ) s ON s.post_id = p.id
+             WHERE s.score > 0 AND p.status = 'published'
+             ORDER BY s.score DESC, p.created_at DESC
+             LIMIT $4"
         ).await?;
 
-    let row = client.query_one(&stmt, &[&post_id]).await.map_err(|_| ServiceError::NotFound)?;
+    let rows = client.query(&stmt, &[&window_days, &half_life_hours, &REACTION_WEIGHT, &limit]).await?;
+
+    let posts = rows
+        .into_iter()
+        .map(|row| PostSummary::from_row_ref(&row).map_err(ServiceError::from))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(row.get(0))
+    Ok(posts)
+}
+
+fn summarize(emojis: &[String], counts: &ReactionCounts, mine: &[String]) -> Vec<ReactionSummary> {
+    emojis
+        .iter()
+        .map(|emoji| ReactionSummary {
+            emoji: emoji.clone(),
+            count: counts.0.get(emoji).copied().unwrap_or(0),
+            reacted: mine.contains(emoji),
+        })
+        .collect()
+}
+
+pub async fn get_reactions(
+    pool: &DbPool,
+    emojis: &[String],
+    post_id: i32,
+    visitor_id: &str,
+) -> Result<Vec<ReactionSummary>, ServiceError> {
+    let client = pool.get().await?;
+
+    let row = client
+        .query_opt("SELECT reactions FROM posts WHERE id = $1", &[&post_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+    let counts: ReactionCounts = row.get(0);
+
+    let rows = client
+        .query(
+            "SELECT emoji FROM post_reactions WHERE post_id = $1 AND visitor_id = $2",
+            &[&post_id, &visitor_id]
+        ).await?;
+    let mine: Vec<String> = rows.into_iter().map(|row| row.get(0)).collect();
+
+    Ok(summarize(emojis, &counts, &mine))
+}
+
+/// 방문자가 이미 남긴 반응이면 취소하고, 아니면 추가합니다.
+/// 쿠키만 지우고 같은 반응을 다시 누르는 경우는 오늘의 방문자 해시로 걸러냅니다.
+pub async fn toggle_reaction(
+    pool: &DbPool,
+    emojis: &[String],
+    post_id: i32,
+    visitor_id: &str,
+    fingerprint: &Fingerprint,
+    emoji: &str,
+) -> Result<Vec<ReactionSummary>, ServiceError> {
+    if !emojis.iter().any(|e| e == emoji) {
+        return Err(ServiceError::BadRequest(format!("지원하지 않는 반응입니다: {}", emoji)));
+    }
+
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    tx.query_opt("SELECT 1 FROM posts WHERE id = $1 AND status = 'published' FOR UPDATE", &[&post_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+
+    let removed = tx
+        .execute(
+            "DELETE FROM post_reactions WHERE post_id = $1 AND emoji = $2 AND visitor_id = $3",
+            &[&post_id, &emoji, &visitor_id]
+        ).await? == 1;
+
+    let delta: i32 = if removed {
+        -1
+    } else {
+        let scope = format!("reaction:{}", emoji);
+        match visitor::record_hit(&tx, &scope, post_id, fingerprint, visitor_id).await? {
+            Some(owner) if owner != visitor_id => 0,
+            _ => {
+                tx.execute(
+                    "INSERT INTO post_reactions (post_id, emoji, visitor_id) VALUES ($1, $2, $3)",
+                    &[&post_id, &emoji, &visitor_id]
+                ).await?;
+                stats_service::record_daily(&tx, post_id, 0, 0, 1).await?;
+                1
+            }
+        }
+    };
+
+    let counts: ReactionCounts = if delta == 0 {
+        tx.query_one("SELECT reactions FROM posts WHERE id = $1", &[&post_id]).await?.get(0)
+    } else {
+        let stmt = tx
+            .prepare_cached(
+                "UPDATE posts SET reactions = CASE
+                     WHEN COALESCE((reactions->>$1)::int, 0) + $2 > 0
+                     THEN jsonb_set(reactions, ARRAY[$1], to_jsonb(COALESCE((reactions->>$1)::int, 0) + $2))
+                     ELSE reactions - $1
+                 END,
+                 reaction_total = reaction_total + $2
+                 WHERE id = $3
+                 RETURNING reactions"
+            ).await?;
+        tx.query_one(&stmt, &[&emoji, &delta, &post_id]).await?.get(0)
+    };
+
+    let rows = tx
+        .query(
+            "SELECT emoji FROM post_reactions WHERE post_id = $1 AND visitor_id = $2",
+            &[&post_id, &visitor_id]
+        ).await?;
+    let mine: Vec<String> = rows.into_iter().map(|row| row.get(0)).collect();
+
+    tx.commit().await?;
+
+    Ok(summarize(emojis, &counts, &mine))
 }
 
 pub async fn create(pool: &DbPool, dto: CreatePost) -> Result<Post, ServiceError> {
@@ -171,50 +632,111 @@ pub async fn create(pool: &DbPool, dto: CreatePost) -> Result<Post, ServiceError
         return Err(ServiceError::BadRequest("대표 이미지를 설정해주세요".into()));
     }
 
-    let client = pool.get().await?;
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+    let tags = tag_service::register(&tx, &dto.tags).await?;
 
-    let stmt = client
+    let stmt = tx
         .prepare_cached(
-            "INSERT INTO posts (title, description, body, tags, thumbnail, thumbnail_blur) \
-         VALUES ($1, $2, $3, $4, $5, $6) \
-         RETURNING id, title, description, body, tags, thumbnail, thumbnail_blur, view_count, like_count, created_at"
+            "INSERT INTO posts (title, description, body, tags, thumbnail, thumbnail_blur, category_id, status) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+         RETURNING id, title, description, body, tags, category_id, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, status, created_at"
         ).await?;
 
-    let row = client
+    let row = tx
         .query_one(
             &stmt,
             &[
                 &dto.title,
                 &dto.description,
                 &dto.body,
-                &dto.tags,
+                &tags,
                 &dto.thumbnail,
                 &dto.thumbnail_blur,
+                &dto.category_id,
+                &dto.status.as_str(),
             ]
-        ).await?;
+        ).await
+        .map_err(unknown_category)?;
 
-    Ok(Post::from_row_ref(&row)?)
+    let post = Post::from_row_ref(&row)?;
+    media_service::sync_usages(&tx, post.id, &post.body, &post.thumbnail, &post.thumbnail_variants).await?;
+    tx.commit().await?;
+
+    Ok(post)
+}
+
+fn unknown_category(e: tokio_postgres::Error) -> ServiceError {
+    if e.code() == Some(&SqlState::FOREIGN_KEY_VIOLATION) {
+        ServiceError::BadRequest("존재하지 않는 카테고리입니다".into())
+    } else {
+        ServiceError::from(e)
+    }
 }
 
 pub async fn update(pool: &DbPool, post_id: i32, dto: UpdatePost) -> Result<Post, ServiceError> {
-    let client = pool.get().await?;
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+    let tags = match &dto.tags {
+        Some(tags) => Some(tag_service::register(&tx, tags).await?),
+        None => None,
+    };
 
-    let stmt = client
+    let stmt = tx
         .prepare_cached(
             "\
         UPDATE posts SET \
             title = COALESCE($1, title), \
             description  = COALESCE($2, description), \
-            body  = COALESCE($3, body) \
+            body  = COALESCE($3, body), \
+            tags  = COALESCE($7, tags), \
+            category_id = CASE WHEN $5 THEN $6 ELSE category_id END, \
+            status = COALESCE($8, status) \
         WHERE id = $4 \
-        RETURNING id, title, description, body, tags, thumbnail, thumbnail_blur, view_count, like_count, created_at"
+        RETURNING id, title, description, body, tags, category_id, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, status, created_at"
         ).await?;
 
-    let row = client
-        .query_one(&stmt, &[&dto.title, &dto.description, &dto.body, &post_id]).await
-        .map_err(|_| ServiceError::NotFound)?;
+    let row = tx
+        .query_opt(
+            &stmt,
+            &[&dto.title, &dto.description, &dto.body, &post_id, &dto.category_id.is_some(), &dto.category_id.flatten(), &tags, &dto.status.map(|status| status.as_str())]
+        ).await
+        .map_err(unknown_category)?
+        .ok_or(ServiceError::NotFound)?;
 
-    Ok(Post::from_row_ref(&row)?)
+    let post = Post::from_row_ref(&row)?;
+    media_service::sync_usages(&tx, post.id, &post.body, &post.thumbnail, &post.thumbnail_variants).await?;
+    tx.commit().await?;
+
+    Ok(post)
+}
+
+pub async fn refresh_thumbnail_variants(
+    pool: &DbPool,
+    storage: &dyn Storage,
+    image_cfg: &ImageConfig,
+    post_id: i32,
+) -> Result<Post, ServiceError> {
+    let post = get_by_id(pool, post_id).await?;
+    let media = media_service::import_url(pool, storage, image_cfg, &post.thumbnail).await?;
+
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    let stmt = tx
+        .prepare_cached(
+            "UPDATE posts SET thumbnail_variants = $1 WHERE id = $2
+             RETURNING id, title, description, body, tags, category_id, thumbnail, thumbnail_blur, thumbnail_variants, view_count, reactions, status, created_at"
+        ).await?;
+
+    let row = tx
+        .query_opt(&stmt, &[&Json(&media.variants.0), &post_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+    let post = Post::from_row_ref(&row)?;
+    media_service::sync_usages(&tx, post.id, &post.body, &post.thumbnail, &post.thumbnail_variants).await?;
+    tx.commit().await?;
+
+    Ok(post)
 }
 
 pub async fn delete(pool: &DbPool, post_id: i32) -> Result<(), ServiceError> {
@@ -0,0 +1,56 @@
+use serde::{ Deserialize, Deserializer, Serialize };
+
+use crate::category::model::Category;
+
+/// 필드가 빠졌으면 `None`, `null` 이면 `Some(None)` 으로 구분합니다.
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+    where T: Deserialize<'de>, D: Deserializer<'de>
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCategory {
+    pub name: String,
+    pub slug: String,
+    pub parent_id: Option<i32>,
+    #[serde(default)]
+    pub position: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCategory {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    /// `null` 을 보내면 최상위로 옮깁니다.
+    #[serde(default, deserialize_with = "double_option")]
+    pub parent_id: Option<Option<i32>>,
+    pub position: Option<i32>,
+}
+
+/// 메뉴용 카테고리 트리
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryNode {
+    #[serde(flatten)]
+    pub category: Category,
+    /// 이 카테고리를 대표 카테고리로 둔 글 수
+    pub post_count: i64,
+    /// 하위 카테고리까지 포함한 글 수
+    pub total_count: i64,
+    pub children: Vec<CategoryNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryRef {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryDetail {
+    #[serde(flatten)]
+    pub node: CategoryNode,
+    /// 최상위부터 이 카테고리까지
+    pub path: Vec<CategoryRef>,
+}
@@ -0,0 +1,61 @@
+use actix_web::{ delete, get, post, put, web, HttpResponse, Responder, ResponseError };
+
+use crate::category::dto::{ CreateCategory, UpdateCategory };
+use crate::category::service;
+use crate::db::DbPool;
+use crate::user::handlers::Admin;
+
+#[get("/categories")]
+pub async fn list_categories(pool: web::Data<DbPool>) -> impl Responder {
+    match service::tree(&pool).await {
+        Ok(tree) => { HttpResponse::Ok().json(tree) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/categories/{slug}")]
+pub async fn get_category(pool: web::Data<DbPool>, path: web::Path<String>) -> impl Responder {
+    let slug = path.into_inner();
+
+    match service::get_by_slug(&pool, &slug).await {
+        Ok(category) => { HttpResponse::Ok().json(category) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/admin/categories")]
+pub async fn create_category(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Json(dto): web::Json<CreateCategory>
+) -> impl Responder {
+    match service::create(&pool, dto).await {
+        Ok(category) => { HttpResponse::Created().json(category) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[put("/admin/categories/{id}")]
+pub async fn update_category(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    path: web::Path<i32>,
+    web::Json(dto): web::Json<UpdateCategory>
+) -> impl Responder {
+    let id = path.into_inner();
+
+    match service::update(&pool, id, dto).await {
+        Ok(category) => { HttpResponse::Ok().json(category) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[delete("/admin/categories/{id}")]
+pub async fn delete_category(_: Admin, pool: web::Data<DbPool>, path: web::Path<i32>) -> impl Responder {
+    let id = path.into_inner();
+
+    match service::delete(&pool, id).await {
+        Ok(_) => { HttpResponse::NoContent().finish() }
+        Err(e) => { e.error_response() }
+    }
+}
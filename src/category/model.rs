@@ -0,0 +1,15 @@
+use chrono::NaiveDateTime;
+use serde::{ Deserialize, Serialize };
+use tokio_pg_mapper_derive::PostgresMapper;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PostgresMapper)]
+#[pg_mapper(table = "categories")]
+pub struct Category {
+    pub id: i32,
+    pub parent_id: Option<i32>,
+    pub name: String,
+    pub slug: String,
+    /// 같은 부모 아래에서의 메뉴 순서
+    pub position: i32,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,10 @@
+use actix_web::web;
+use crate::category::handlers;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(handlers::list_categories)
+        .service(handlers::get_category)
+        .service(handlers::create_category)
+        .service(handlers::update_category)
+        .service(handlers::delete_category);
+}
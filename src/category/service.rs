@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use deadpool_postgres::GenericClient;
+use tokio_pg_mapper::FromTokioPostgresRow;
+
+use crate::category::dto::{ CategoryDetail, CategoryNode, CategoryRef, CreateCategory, UpdateCategory };
+use crate::category::model::Category;
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::slug::{ slug_conflict, validate_slug };
+
+const CATEGORY_COLUMNS: &str = "id, parent_id, name, slug, position, created_at";
+
+const MAX_NAME_CHARS: usize = 100;
+
+fn validate_name(name: &str) -> Result<String, ServiceError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ServiceError::BadRequest("카테고리 이름을 입력해주세요".into()));
+    }
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(ServiceError::BadRequest(format!("카테고리 이름은 {}자를 넘을 수 없습니다", MAX_NAME_CHARS)));
+    }
+    Ok(name.to_string())
+}
+
+async fn load_nodes(client: &impl GenericClient) -> Result<Vec<(Category, i64)>, ServiceError> {
+    let rows = client
+        .query(
+            "SELECT c.id, c.parent_id, c.name, c.slug, c.position, c.created_at, COUNT(p.id) AS post_count
+             FROM categories c
+             LEFT JOIN posts p ON p.category_id = c.id AND p.status = 'published'
+             GROUP BY c.id
+             ORDER BY c.position, c.name, c.id",
+            &[]
+        ).await?;
+
+    rows.into_iter()
+        .map(|row| Ok((Category::from_row_ref(&row)?, row.get("post_count"))))
+        .collect()
+}
+
+fn build_tree(nodes: Vec<(Category, i64)>) -> Vec<CategoryNode> {
+    let mut children: HashMap<Option<i32>, Vec<(Category, i64)>> = HashMap::new();
+    for node in nodes {
+        children.entry(node.0.parent_id).or_default().push(node);
+    }
+
+    fn build(parent: Option<i32>, children: &mut HashMap<Option<i32>, Vec<(Category, i64)>>) -> Vec<CategoryNode> {
+        let Some(nodes) = children.remove(&parent) else {
+            return Vec::new();
+        };
+        nodes
+            .into_iter()
+            .map(|(category, post_count)| {
+                let children = build(Some(category.id), children);
+                let total_count = post_count + children.iter().map(|c| c.total_count).sum::<i64>();
+                CategoryNode { category, post_count, total_count, children }
+            })
+            .collect()
+    }
+
+    build(None, &mut children)
+}
+
+pub async fn tree(pool: &DbPool) -> Result<Vec<CategoryNode>, ServiceError> {
+    let client = pool.get().await?;
+    Ok(build_tree(load_nodes(&client).await?))
+}
+
+fn find_node(nodes: Vec<CategoryNode>, slug: &str, path: &mut Vec<CategoryRef>) -> Option<CategoryNode> {
+    for node in nodes {
+        path.push(CategoryRef { id: node.category.id, name: node.category.name.clone(), slug: node.category.slug.clone() });
+        if node.category.slug == slug {
+            return Some(node);
+        }
+        if let Some(found) = find_node(node.children, slug, path) {
+            return Some(found);
+        }
+        path.pop();
+    }
+    None
+}
+
+pub async fn get_by_slug(pool: &DbPool, slug: &str) -> Result<CategoryDetail, ServiceError> {
+    let client = pool.get().await?;
+    let nodes = build_tree(load_nodes(&client).await?);
+
+    let mut path = Vec::new();
+    let node = find_node(nodes, slug, &mut path).ok_or(ServiceError::NotFound)?;
+    Ok(CategoryDetail { node, path })
+}
+
+/// 카테고리와 모든 하위 카테고리의 ID. 없는 슬러그면 `NotFound` 를 돌려줍니다.
+pub async fn subtree_ids(client: &impl GenericClient, slug: &str) -> Result<Vec<i32>, ServiceError> {
+    let rows = client
+        .query(
+            "WITH RECURSIVE tree AS (
+                 SELECT id FROM categories WHERE slug = $1
+                 UNION ALL
+                 SELECT c.id FROM categories c JOIN tree t ON c.parent_id = t.id
+             )
+             SELECT id FROM tree",
+            &[&slug]
+        ).await?;
+    if rows.is_empty() {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(rows.into_iter().map(|row| row.get(0)).collect())
+}
+
+/// 최상위부터 이 카테고리까지의 경로
+pub async fn path_for(client: &impl GenericClient, category_id: i32) -> Result<Vec<CategoryRef>, ServiceError> {
+    let rows = client
+        .query(
+            "WITH RECURSIVE path AS (
+                 SELECT id, parent_id, name, slug, 0 AS depth FROM categories WHERE id = $1
+                 UNION ALL
+                 SELECT c.id, c.parent_id, c.name, c.slug, p.depth + 1
+                 FROM categories c JOIN path p ON c.id = p.parent_id
+             )
+             SELECT id, name, slug FROM path ORDER BY depth DESC",
+            &[&category_id]
+        ).await?;
+
+    Ok(rows.into_iter().map(|row| CategoryRef { id: row.get(0), name: row.get(1), slug: row.get(2) }).collect())
+}
+
+async fn check_parent(client: &impl GenericClient, category_id: Option<i32>, parent_id: i32) -> Result<(), ServiceError> {
+    let path = path_for(client, parent_id).await?;
+    if path.is_empty() {
+        return Err(ServiceError::BadRequest("상위 카테고리가 없습니다".into()));
+    }
+    if let Some(id) = category_id
+        && path.iter().any(|c| c.id == id)
+    {
+        return Err(ServiceError::BadRequest("자기 자신이나 하위 카테고리 아래로 옮길 수 없습니다".into()));
+    }
+    Ok(())
+}
+
+pub async fn create(pool: &DbPool, dto: CreateCategory) -> Result<Category, ServiceError> {
+    let name = validate_name(&dto.name)?;
+    let slug = validate_slug(&dto.slug)?;
+
+    let client = pool.get().await?;
+
+    if let Some(parent_id) = dto.parent_id {
+        check_parent(&client, None, parent_id).await?;
+    }
+
+    let row = client
+        .query_one(
+            &format!(
+                "INSERT INTO categories (parent_id, name, slug, position) VALUES ($1, $2, $3, $4) RETURNING {}",
+                CATEGORY_COLUMNS
+            ),
+            &[&dto.parent_id, &name, &slug, &dto.position]
+        ).await
+        .map_err(slug_conflict)?;
+
+    Ok(Category::from_row_ref(&row)?)
+}
+
+pub async fn update(pool: &DbPool, category_id: i32, dto: UpdateCategory) -> Result<Category, ServiceError> {
+    let name = dto.name.as_deref().map(validate_name).transpose()?;
+    let slug = dto.slug.as_deref().map(validate_slug).transpose()?;
+
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    // 동시에 부모를 바꾸다 순환이 생기지 않도록 한 번에 하나씩 옮깁니다.
+    tx.execute("LOCK TABLE categories IN SHARE ROW EXCLUSIVE MODE", &[]).await?;
+
+    let row = tx
+        .query_opt(&format!("SELECT {} FROM categories WHERE id = $1", CATEGORY_COLUMNS), &[&category_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+    let current = Category::from_row_ref(&row)?;
+
+    let parent_id = match dto.parent_id {
+        Some(Some(parent_id)) => {
+            check_parent(&tx, Some(category_id), parent_id).await?;
+            Some(parent_id)
+        }
+        Some(None) => None,
+        None => current.parent_id,
+    };
+
+    let row = tx
+        .query_one(
+            &format!(
+                "UPDATE categories SET
+                     parent_id = $1,
+                     name = COALESCE($2, name),
+                     slug = COALESCE($3, slug),
+                     position = COALESCE($4, position)
+                 WHERE id = $5
+                 RETURNING {}",
+                CATEGORY_COLUMNS
+            ),
+            &[&parent_id, &name, &slug, &dto.position, &category_id]
+        ).await
+        .map_err(slug_conflict)?;
+    tx.commit().await?;
+
+    Ok(Category::from_row_ref(&row)?)
+}
+
+/// 하위 카테고리가 있으면 지우지 않습니다. 이 카테고리의 글은 미분류가 됩니다.
+pub async fn delete(pool: &DbPool, category_id: i32) -> Result<(), ServiceError> {
+    let client = pool.get().await?;
+
+    let has_children = client
+        .query_opt("SELECT 1 FROM categories WHERE parent_id = $1 LIMIT 1", &[&category_id]).await?
+        .is_some();
+    if has_children {
+        return Err(ServiceError::BadRequest("하위 카테고리를 먼저 옮기거나 지워주세요".into()));
+    }
+
+    let deleted = client.execute("DELETE FROM categories WHERE id = $1", &[&category_id]).await?;
+    if deleted == 0 {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::comment::model::Comment;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateComment {
+    pub parent_id: Option<i32>,
+    #[serde(default)]
+    pub nickname: String,
+    #[serde(default)]
+    pub password: String,
+    pub body: String,
+    /// 허니팟 필드. 폼에서 숨겨두고 비워서 보내야 합니다.
+    #[serde(default)]
+    pub website: String,
+    pub form_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateComment {
+    #[serde(default)]
+    pub password: String,
+    pub body: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteComment {
+    #[serde(default)]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentThread {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub replies: Vec<CommentThread>,
+}
+
+/// 모더레이션 큐의 댓글. 관리자에게만 스팸 의심 사유를 함께 보여줍니다.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModerationComment {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub spam_reasons: Vec<String>,
+}
+
+impl From<Comment> for ModerationComment {
+    fn from(comment: Comment) -> Self {
+        let spam_reasons = comment.spam_reasons.clone();
+        ModerationComment { comment, spam_reasons }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentListResponse {
+    pub total_count: i64,
+    pub comments: Vec<ModerationComment>,
+}
@@ -0,0 +1,152 @@
+use actix_web::{ delete, get, post, put, web, HttpRequest, HttpResponse, Responder, ResponseError };
+use serde::Deserialize;
+
+use crate::comment::dto::{ CreateComment, DeleteComment, UpdateComment };
+use crate::comment::model::CommentStatus;
+use crate::comment::service;
+use crate::comment::spam::{ SpamFilter, Submission };
+use crate::config::AppConfig;
+use crate::db::DbPool;
+use crate::user::handlers::{ auth_from_cookie, Admin };
+use crate::user::model::Role;
+use crate::visitor::{ client_ip, user_agent };
+
+#[derive(Debug, Deserialize)]
+struct ModerationQuery {
+    status: Option<CommentStatus>,
+    page: Option<u32>,
+    #[serde(rename = "pageSize")]
+    page_size: Option<u32>,
+}
+
+#[get("/posts/{id}/comments")]
+pub async fn list_comments(pool: web::Data<DbPool>, path: web::Path<i32>) -> impl Responder {
+    let post_id = path.into_inner();
+
+    match service::list_for_post(&pool, post_id).await {
+        Ok(threads) => { HttpResponse::Ok().json(threads) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/posts/{id}/comments/token")]
+pub async fn comment_form_token(spam: web::Data<SpamFilter>, path: web::Path<i32>) -> impl Responder {
+    let post_id = path.into_inner();
+    HttpResponse::Ok().json(spam.issue_token(post_id))
+}
+
+#[post("/posts/{id}/comments")]
+pub async fn create_comment(
+    req: HttpRequest,
+    cfg: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    spam: web::Data<SpamFilter>,
+    path: web::Path<i32>,
+    web::Json(dto): web::Json<CreateComment>
+) -> impl Responder {
+    let post_id = path.into_inner();
+    let user = auth_from_cookie(&req, &cfg);
+    let author = (user.role == Role::Admin).then_some(user.username.as_str());
+
+    let mut spam_reasons = Vec::new();
+    if author.is_none() {
+        let ip = client_ip(&req);
+        if let Err(e) = spam.check_rate(&ip) {
+            return e.error_response();
+        }
+
+        let submission = Submission {
+            post_id,
+            ip: &ip,
+            user_agent: user_agent(&req),
+            nickname: &dto.nickname,
+            body: &dto.body,
+            honeypot: &dto.website,
+            form_token: dto.form_token.as_deref(),
+        };
+        spam_reasons = spam.inspect(&submission).await;
+    }
+
+    match service::create(&pool, post_id, dto, author, cfg.comment.require_approval, &spam_reasons).await {
+        Ok(comment) => { HttpResponse::Created().json(comment) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[put("/comments/{id}")]
+pub async fn update_comment(
+    req: HttpRequest,
+    cfg: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    spam: web::Data<SpamFilter>,
+    path: web::Path<i32>,
+    web::Json(dto): web::Json<UpdateComment>
+) -> impl Responder {
+    let comment_id = path.into_inner();
+    let is_admin = auth_from_cookie(&req, &cfg).role == Role::Admin;
+    let ip = client_ip(&req);
+    if !is_admin && let Err(e) = spam.check_password_attempt(&ip, comment_id) {
+        return e.error_response();
+    }
+
+    match service::update(&pool, &spam, comment_id, dto, is_admin, &ip, user_agent(&req)).await {
+        Ok(comment) => { HttpResponse::Ok().json(comment) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[delete("/comments/{id}")]
+pub async fn delete_comment(
+    req: HttpRequest,
+    cfg: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    spam: web::Data<SpamFilter>,
+    path: web::Path<i32>,
+    dto: Option<web::Json<DeleteComment>>
+) -> impl Responder {
+    let comment_id = path.into_inner();
+    let is_admin = auth_from_cookie(&req, &cfg).role == Role::Admin;
+    if !is_admin && let Err(e) = spam.check_password_attempt(&client_ip(&req), comment_id) {
+        return e.error_response();
+    }
+    let dto = dto.map(|json| json.into_inner()).unwrap_or_default();
+
+    match service::delete(&pool, comment_id, dto, is_admin).await {
+        Ok(_) => { HttpResponse::NoContent().finish() }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/admin/comments")]
+pub async fn moderation_queue(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<ModerationQuery>
+) -> impl Responder {
+    let page_size = query.page_size.unwrap_or(20).max(1) as i64;
+    let offset = ((query.page.unwrap_or(1).max(1) as i64) - 1) * page_size;
+    let status = query.status.unwrap_or(CommentStatus::Pending);
+
+    match service::list_by_status(&pool, status, page_size, offset).await {
+        Ok(data) => { HttpResponse::Ok().json(data) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/admin/comments/{id}/approve")]
+pub async fn approve_comment(_: Admin, pool: web::Data<DbPool>, path: web::Path<i32>) -> impl Responder {
+    let comment_id = path.into_inner();
+    match service::set_status(&pool, comment_id, CommentStatus::Approved).await {
+        Ok(comment) => { HttpResponse::Ok().json(comment) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/admin/comments/{id}/reject")]
+pub async fn reject_comment(_: Admin, pool: web::Data<DbPool>, path: web::Path<i32>) -> impl Responder {
+    let comment_id = path.into_inner();
+    match service::set_status(&pool, comment_id, CommentStatus::Rejected).await {
+        Ok(comment) => { HttpResponse::Ok().json(comment) }
+        Err(e) => { e.error_response() }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod model;
+pub mod dto;
+pub mod password;
+pub mod spam;
+pub mod service;
+pub mod handlers;
+pub mod routes;
@@ -0,0 +1,57 @@
+use std::error::Error;
+
+use chrono::NaiveDateTime;
+use serde::{ Deserialize, Serialize };
+use tokio_pg_mapper_derive::PostgresMapper;
+use tokio_postgres::types::{ FromSql, Type };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl CommentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl<'a> FromSql<'a> for CommentStatus {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match <&str as FromSql>::from_sql(ty, raw)? {
+            "pending" => Ok(CommentStatus::Pending),
+            "approved" => Ok(CommentStatus::Approved),
+            "rejected" => Ok(CommentStatus::Rejected),
+            other => Err(format!("unknown comment status: {}", other).into()),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PostgresMapper)]
+#[pg_mapper(table = "comments")]
+pub struct Comment {
+    pub id: i32,
+    pub post_id: i32,
+    pub parent_id: Option<i32>,
+    pub nickname: String,
+    pub body: String,
+    pub is_author: bool,
+    pub status: CommentStatus,
+    pub deleted: bool,
+    /// 스팸 검사에 걸린 사유. 방문자에게는 내보내지 않고 모더레이션 큐에서만 보여줍니다.
+    #[serde(default, skip_serializing)]
+    pub spam_reasons: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
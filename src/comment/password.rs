@@ -0,0 +1,39 @@
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use sha2::Sha256;
+
+const ROUNDS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+
+/// 익명 댓글 비밀번호를 `pbkdf2-sha256$반복횟수$salt$hash` 형태로 저장합니다.
+pub fn hash(password: &str) -> String {
+    let mut salt = [0u8; SALT_LEN];
+    rand::rng().fill_bytes(&mut salt);
+
+    let mut out = [0u8; HASH_LEN];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, ROUNDS, &mut out);
+
+    format!("pbkdf2-sha256${}${}${}", ROUNDS, hex::encode(salt), hex::encode(out))
+}
+
+pub fn verify(password: &str, stored: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (Some("pbkdf2-sha256"), Some(rounds), Some(salt), Some(expected), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return false;
+    };
+    let (Ok(rounds), Ok(salt), Ok(expected)) = (rounds.parse::<u32>(), hex::decode(salt), hex::decode(expected)) else {
+        return false;
+    };
+
+    let mut out = vec![0u8; expected.len()];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, rounds, &mut out);
+
+    out.len() == expected.len() && out.iter().zip(&expected).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
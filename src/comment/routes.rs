@@ -0,0 +1,13 @@
+use actix_web::web;
+use crate::comment::handlers;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(handlers::list_comments)
+        .service(handlers::comment_form_token)
+        .service(handlers::create_comment)
+        .service(handlers::update_comment)
+        .service(handlers::delete_comment)
+        .service(handlers::moderation_queue)
+        .service(handlers::approve_comment)
+        .service(handlers::reject_comment);
+}
@@ -0,0 +1,342 @@
+use std::collections::{ HashMap, HashSet };
+
+use actix_web::web;
+use tokio_pg_mapper::FromTokioPostgresRow;
+
+use crate::comment::dto::{
+    CommentListResponse,
+    CommentThread,
+    CreateComment,
+    DeleteComment,
+    ModerationComment,
+    UpdateComment,
+};
+use crate::comment::model::{ Comment, CommentStatus };
+use crate::blog::service as blog_service;
+use crate::comment::password;
+use crate::comment::spam::{ SpamFilter, Submission };
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+
+const COMMENT_COLUMNS: &str =
+    "id, post_id, parent_id, nickname, body, is_author, status, deleted, spam_reasons, created_at, updated_at";
+
+const MAX_NICKNAME_CHARS: usize = 30;
+const MIN_PASSWORD_CHARS: usize = 8;
+const MAX_BODY_CHARS: usize = 2000;
+
+fn validate_body(body: &str) -> Result<(), ServiceError> {
+    if body.trim().is_empty() {
+        return Err(ServiceError::BadRequest("댓글 내용을 입력해주세요".into()));
+    }
+    if body.chars().count() > MAX_BODY_CHARS {
+        return Err(ServiceError::BadRequest(format!("댓글은 {}자를 넘을 수 없습니다", MAX_BODY_CHARS)));
+    }
+    Ok(())
+}
+
+/// 부모가 승인되지 않았거나 거절된 답글은 버리지 않고 최상위 스레드로 올립니다.
+fn build_threads(comments: Vec<Comment>) -> Vec<CommentThread> {
+    let loaded: HashSet<i32> = comments.iter().map(|comment| comment.id).collect();
+    let mut children: HashMap<Option<i32>, Vec<Comment>> = HashMap::new();
+    for comment in comments {
+        children.entry(comment.parent_id).or_default().push(comment);
+    }
+
+    fn build(parent: Option<i32>, children: &mut HashMap<Option<i32>, Vec<Comment>>) -> Vec<CommentThread> {
+        let Some(comments) = children.remove(&parent) else {
+            return Vec::new();
+        };
+        comments
+            .into_iter()
+            .filter_map(|comment| {
+                let replies = build(Some(comment.id), children);
+                // 답글이 모두 사라진 삭제 댓글은 더 보여줄 이유가 없습니다.
+                if comment.deleted && replies.is_empty() {
+                    None
+                } else {
+                    Some(CommentThread { comment, replies })
+                }
+            })
+            .collect()
+    }
+
+    let mut threads = build(None, &mut children);
+    let orphaned: Vec<Option<i32>> = children
+        .keys()
+        .filter(|parent| parent.is_some_and(|id| !loaded.contains(&id)))
+        .copied()
+        .collect();
+    for parent in orphaned {
+        threads.extend(build(parent, &mut children));
+    }
+    threads.sort_by_key(|thread| (thread.comment.created_at, thread.comment.id));
+    threads
+}
+
+pub async fn list_for_post(pool: &DbPool, post_id: i32) -> Result<Vec<CommentThread>, ServiceError> {
+    let client = pool.get().await?;
+
+    client
+        .query_opt("SELECT 1 FROM posts WHERE id = $1", &[&post_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "SELECT {} FROM comments
+                 WHERE post_id = $1 AND status = 'approved'
+                 ORDER BY created_at, id",
+                COMMENT_COLUMNS
+            )
+        ).await?;
+    let rows = client.query(&stmt, &[&post_id]).await?;
+
+    let comments = rows
+        .into_iter()
+        .map(|row| Comment::from_row_ref(&row).map_err(ServiceError::from))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(build_threads(comments))
+}
+
+/// `author` 가 있으면 관리자 댓글로 저장하고 비밀번호와 승인 절차를 건너뜁니다.
+/// 스팸 의심 사유가 있으면 승인 대기로 저장합니다.
+pub async fn create(
+    pool: &DbPool,
+    post_id: i32,
+    dto: CreateComment,
+    author: Option<&str>,
+    require_approval: bool,
+    spam_reasons: &[String]
+) -> Result<Comment, ServiceError> {
+    validate_body(&dto.body)?;
+
+    let nickname = match author {
+        Some(username) if dto.nickname.trim().is_empty() => username.to_string(),
+        _ => dto.nickname.trim().to_string(),
+    };
+    if nickname.is_empty() {
+        return Err(ServiceError::BadRequest("닉네임을 입력해주세요".into()));
+    }
+    if nickname.chars().count() > MAX_NICKNAME_CHARS {
+        return Err(ServiceError::BadRequest(format!("닉네임은 {}자를 넘을 수 없습니다", MAX_NICKNAME_CHARS)));
+    }
+
+    let password_hash = if author.is_some() {
+        String::new()
+    } else {
+        if dto.password.chars().count() < MIN_PASSWORD_CHARS {
+            return Err(
+                ServiceError::BadRequest(format!("비밀번호는 {}자 이상이어야 합니다", MIN_PASSWORD_CHARS))
+            );
+        }
+        let password = dto.password.clone();
+        web::block(move || password::hash(&password)).await?
+    };
+
+    let status = if author.is_none() && (require_approval || !spam_reasons.is_empty()) {
+        CommentStatus::Pending
+    } else {
+        CommentStatus::Approved
+    };
+
+    let client = pool.get().await?;
+
+    blog_service::ensure_published(&client, post_id).await?;
+
+    if let Some(parent_id) = dto.parent_id {
+        let parent = client
+            .query_opt("SELECT post_id, status, deleted FROM comments WHERE id = $1", &[&parent_id]).await?
+            .ok_or_else(|| ServiceError::BadRequest("답글을 달 댓글이 없습니다".into()))?;
+        let (parent_post_id, parent_status, parent_deleted): (i32, CommentStatus, bool) = (
+            parent.get(0),
+            parent.get(1),
+            parent.get(2),
+        );
+        if parent_post_id != post_id || parent_status != CommentStatus::Approved || parent_deleted {
+            return Err(ServiceError::BadRequest("답글을 달 수 없는 댓글입니다".into()));
+        }
+    }
+
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "INSERT INTO comments (post_id, parent_id, nickname, password_hash, body, is_author, status, spam_reasons)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                 RETURNING {}",
+                COMMENT_COLUMNS
+            )
+        ).await?;
+
+    let row = client
+        .query_one(
+            &stmt,
+            &[
+                &post_id,
+                &dto.parent_id,
+                &nickname,
+                &password_hash,
+                &dto.body.trim(),
+                &author.is_some(),
+                &status.as_str(),
+                &spam_reasons,
+            ]
+        ).await?;
+
+    Ok(Comment::from_row_ref(&row)?)
+}
+
+/// 관리자가 아니면 작성 시 입력한 비밀번호가 맞아야 합니다. 관리자 댓글은 관리자만 다룰 수 있습니다.
+async fn authorize(
+    client: &deadpool_postgres::Client,
+    comment_id: i32,
+    password: &str,
+    is_admin: bool
+) -> Result<Comment, ServiceError> {
+    let stmt = client
+        .prepare_cached(&format!("SELECT {}, password_hash FROM comments WHERE id = $1", COMMENT_COLUMNS)).await?;
+    let row = client.query_opt(&stmt, &[&comment_id]).await?.ok_or(ServiceError::NotFound)?;
+    let comment = Comment::from_row_ref(&row)?;
+
+    if comment.deleted {
+        return Err(ServiceError::NotFound);
+    }
+    if is_admin {
+        return Ok(comment);
+    }
+
+    let password_hash: String = row.get("password_hash");
+    if comment.is_author || password_hash.is_empty() {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let password = password.to_string();
+    let verified = web::block(move || password::verify(&password, &password_hash)).await?;
+    if !verified {
+        return Err(ServiceError::Forbidden("비밀번호가 일치하지 않습니다".into()));
+    }
+
+    Ok(comment)
+}
+
+/// 방문자가 고친 본문은 스팸 규칙을 다시 거치고, 걸리면 승인 대기로 돌아갑니다.
+pub async fn update(
+    pool: &DbPool,
+    spam: &SpamFilter,
+    comment_id: i32,
+    dto: UpdateComment,
+    is_admin: bool,
+    ip: &str,
+    user_agent: &str
+) -> Result<Comment, ServiceError> {
+    validate_body(&dto.body)?;
+
+    let client = pool.get().await?;
+    let comment = authorize(&client, comment_id, &dto.password, is_admin).await?;
+
+    let spam_reasons = if is_admin {
+        Vec::new()
+    } else {
+        let submission = Submission {
+            post_id: comment.post_id,
+            ip,
+            user_agent,
+            nickname: &comment.nickname,
+            body: &dto.body,
+            honeypot: "",
+            form_token: None,
+        };
+        spam.inspect_content(&submission).await
+    };
+
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "UPDATE comments
+                 SET body = $1, updated_at = NOW(),
+                     status = CASE WHEN cardinality($3::text[]) > 0 THEN 'pending' ELSE status END,
+                     spam_reasons = CASE WHEN cardinality($3::text[]) > 0 THEN $3 ELSE spam_reasons END
+                 WHERE id = $2
+                 RETURNING {}",
+                COMMENT_COLUMNS
+            )
+        ).await?;
+    let row = client.query_one(&stmt, &[&dto.body.trim(), &comment_id, &spam_reasons]).await?;
+
+    Ok(Comment::from_row_ref(&row)?)
+}
+
+/// 답글이 달린 댓글은 스레드가 끊기지 않도록 내용만 지웁니다.
+pub async fn delete(
+    pool: &DbPool,
+    comment_id: i32,
+    dto: DeleteComment,
+    is_admin: bool
+) -> Result<(), ServiceError> {
+    let client = pool.get().await?;
+    authorize(&client, comment_id, &dto.password, is_admin).await?;
+
+    let has_replies = client
+        .query_opt("SELECT 1 FROM comments WHERE parent_id = $1 LIMIT 1", &[&comment_id]).await?
+        .is_some();
+
+    if has_replies {
+        client
+            .execute(
+                "UPDATE comments SET deleted = TRUE, body = '', updated_at = NOW() WHERE id = $1",
+                &[&comment_id]
+            ).await?;
+    } else {
+        client.execute("DELETE FROM comments WHERE id = $1", &[&comment_id]).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn list_by_status(
+    pool: &DbPool,
+    status: CommentStatus,
+    limit: i64,
+    offset: i64
+) -> Result<CommentListResponse, ServiceError> {
+    let client = pool.get().await?;
+
+    let count_row = client
+        .query_one("SELECT COUNT(*) FROM comments WHERE status = $1", &[&status.as_str()]).await?;
+    let total_count: i64 = count_row.get(0);
+
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "SELECT {} FROM comments
+                 WHERE status = $1
+                 ORDER BY created_at DESC, id DESC
+                 OFFSET $2
+                 LIMIT  $3",
+                COMMENT_COLUMNS
+            )
+        ).await?;
+    let rows = client.query(&stmt, &[&status.as_str(), &offset, &limit]).await?;
+
+    let comments = rows
+        .into_iter()
+        .map(|row| Comment::from_row_ref(&row).map(ModerationComment::from).map_err(ServiceError::from))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(CommentListResponse { total_count, comments })
+}
+
+pub async fn set_status(pool: &DbPool, comment_id: i32, status: CommentStatus) -> Result<Comment, ServiceError> {
+    let client = pool.get().await?;
+
+    let stmt = client
+        .prepare_cached(
+            &format!("UPDATE comments SET status = $1 WHERE id = $2 RETURNING {}", COMMENT_COLUMNS)
+        ).await?;
+    let row = client
+        .query_opt(&stmt, &[&status.as_str(), &comment_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+
+    Ok(Comment::from_row_ref(&row)?)
+}
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{ Hmac, Mac };
+use serde::{ Deserialize, Serialize };
+use sha2::Sha256;
+
+use crate::config::CommentConfig;
+use crate::errors::ServiceError;
+use crate::visitor::RateLimiter;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 스팸 검사 대상 댓글
+pub struct Submission<'a> {
+    pub post_id: i32,
+    pub ip: &'a str,
+    pub user_agent: &'a str,
+    pub nickname: &'a str,
+    pub body: &'a str,
+    /// 사람에게는 보이지 않는 폼 필드. 값이 있으면 봇입니다.
+    pub honeypot: &'a str,
+    pub form_token: Option<&'a str>,
+}
+
+/// 외부 스팸 판별기(Akismet 등)를 붙이기 위한 확장 지점입니다.
+#[async_trait]
+pub trait SpamClassifier: Send + Sync {
+    /// 스팸으로 의심되면 그 사유를 돌려줍니다.
+    async fn classify(&self, submission: &Submission<'_>) -> Result<Option<String>, ServiceError>;
+}
+
+pub struct NoopClassifier;
+
+#[async_trait]
+impl SpamClassifier for NoopClassifier {
+    async fn classify(&self, _: &Submission<'_>) -> Result<Option<String>, ServiceError> {
+        Ok(None)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormToken {
+    pub token: String,
+    pub min_submit_secs: u64,
+}
+
+pub struct SpamFilter {
+    cfg: CommentConfig,
+    banned_words: Vec<String>,
+    classifier: Box<dyn SpamClassifier>,
+    rate: RateLimiter,
+    password_attempts: RateLimiter,
+}
+
+impl SpamFilter {
+    pub fn new(cfg: &CommentConfig) -> Self {
+        Self::with_classifier(cfg, Box::new(NoopClassifier))
+    }
+
+    pub fn with_classifier(cfg: &CommentConfig, classifier: Box<dyn SpamClassifier>) -> Self {
+        SpamFilter {
+            cfg: cfg.clone(),
+            banned_words: cfg.banned_words(),
+            classifier,
+            rate: RateLimiter::new(cfg.rate_limit_count, Duration::from_secs(cfg.rate_limit_window_secs)),
+            password_attempts: RateLimiter::new(
+                cfg.password_attempt_limit,
+                Duration::from_secs(cfg.password_attempt_window_secs)
+            ),
+        }
+    }
+
+    fn mac(&self, post_id: i32, issued_at: i64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(self.cfg.form_secret.as_bytes()).expect(
+            "HMAC accepts keys of any length"
+        );
+        mac.update(format!("comment-form:{}:{}", post_id, issued_at).as_bytes());
+        mac
+    }
+
+    /// 댓글 폼을 띄울 때 발급하는 토큰. `{발급시각}.{서명}` 형태입니다.
+    pub fn issue_token(&self, post_id: i32) -> FormToken {
+        let issued_at = Utc::now().timestamp();
+        let sig = hex::encode(self.mac(post_id, issued_at).finalize().into_bytes());
+        FormToken { token: format!("{}.{}", issued_at, sig), min_submit_secs: self.cfg.min_submit_secs }
+    }
+
+    fn check_token(&self, post_id: i32, token: Option<&str>) -> Option<String> {
+        let Some(token) = token.filter(|t| !t.is_empty()) else {
+            return Some("폼 토큰 없음".into());
+        };
+        let Some((issued_at, sig)) = token.split_once('.') else {
+            return Some("잘못된 폼 토큰".into());
+        };
+        let (Ok(issued_at), Ok(sig)) = (issued_at.parse::<i64>(), hex::decode(sig)) else {
+            return Some("잘못된 폼 토큰".into());
+        };
+        if self.mac(post_id, issued_at).verify_slice(&sig).is_err() {
+            return Some("잘못된 폼 토큰".into());
+        }
+
+        let elapsed = Utc::now().timestamp() - issued_at;
+        if elapsed < (self.cfg.min_submit_secs as i64) {
+            return Some(format!("너무 빠른 제출 ({}초)", elapsed.max(0)));
+        }
+        if elapsed > (self.cfg.form_token_ttl_secs as i64) {
+            return Some("만료된 폼 토큰".into());
+        }
+        None
+    }
+
+    /// IP 당 제출 횟수를 넘으면 모더레이션 큐를 채우지 않도록 바로 거절합니다.
+    pub fn check_rate(&self, ip: &str) -> Result<(), ServiceError> {
+        if self.rate.allow(ip) { Ok(()) } else { Err(ServiceError::TooManyRequests) }
+    }
+
+    /// 짧은 비밀번호를 대입해 남의 댓글을 고치거나 지우지 못하도록 IP 와 댓글마다 시도 횟수를 제한합니다.
+    pub fn check_password_attempt(&self, ip: &str, comment_id: i32) -> Result<(), ServiceError> {
+        if self.password_attempts.allow(&format!("{ip}:{comment_id}")) {
+            Ok(())
+        } else {
+            Err(ServiceError::TooManyRequests)
+        }
+    }
+
+    /// 스팸으로 의심되는 사유를 모두 모읍니다. 비어 있지 않으면 댓글은 승인 대기로 저장됩니다.
+    pub async fn inspect(&self, submission: &Submission<'_>) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        if !submission.honeypot.trim().is_empty() {
+            reasons.push("허니팟 입력".into());
+        }
+        if let Some(reason) = self.check_token(submission.post_id, submission.form_token) {
+            reasons.push(reason);
+        }
+        reasons.extend(self.content_reasons(submission).await);
+
+        log_reasons(submission, &reasons);
+        reasons
+    }
+
+    /// 폼 토큰이나 허니팟 없이 본문만 다시 봅니다. 댓글을 수정할 때 씁니다.
+    pub async fn inspect_content(&self, submission: &Submission<'_>) -> Vec<String> {
+        let reasons = self.content_reasons(submission).await;
+        log_reasons(submission, &reasons);
+        reasons
+    }
+
+    async fn content_reasons(&self, submission: &Submission<'_>) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        let links = count_links(submission.body);
+        if links > self.cfg.max_links {
+            reasons.push(format!("링크 {}개", links));
+        }
+
+        let text = format!("{} {}", submission.nickname, submission.body).to_lowercase();
+        if let Some(word) = self.banned_words.iter().find(|w| text.contains(w.as_str())) {
+            reasons.push(format!("금칙어: {}", word));
+        }
+
+        match self.classifier.classify(submission).await {
+            Ok(Some(reason)) => reasons.push(reason),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("스팸 판별기 호출 실패: {e}"),
+        }
+        reasons
+    }
+}
+
+fn log_reasons(submission: &Submission<'_>, reasons: &[String]) {
+    if !reasons.is_empty() {
+        tracing::info!(
+            "스팸 의심 댓글 (post {}, ip {}, ua {:?}): {}",
+            submission.post_id,
+            submission.ip,
+            submission.user_agent,
+            reasons.join(", ")
+        );
+    }
+}
+
+fn count_links(body: &str) -> usize {
+    body.split_whitespace()
+        .filter(|word| {
+            let word = word.to_lowercase();
+            word.contains("://") || word.starts_with("www.") || word.contains("href=")
+        })
+        .count()
+}
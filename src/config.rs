@@ -20,6 +20,226 @@ pub struct AppConfig {
 
     #[confik(from = DbConfig)]
     pub pg: deadpool_postgres::Config,
+
+    pub storage: StorageConfig,
+
+    pub image: ImageConfig,
+
+    pub comment: CommentConfig,
+
+    pub reaction: ReactionConfig,
+
+    pub stats: StatsConfig,
+}
+
+#[derive(Debug, Default, Configuration, Clone)]
+pub struct StorageConfig {
+    #[confik(default = StorageBackend::Local)]
+    pub backend: StorageBackend,
+
+    #[confik(default = "./uploads".to_string())]
+    pub local_dir: String,
+
+    /// 업로드된 파일의 공개 URL 접두사. S3 백엔드에서 비워두면 `{endpoint}/{bucket}` 을 사용합니다.
+    #[confik(default = "/media/files".to_string())]
+    pub public_base_url: String,
+
+    #[confik(default = 10_485_760_usize)]
+    pub max_upload_bytes: usize,
+
+    pub s3_endpoint: Option<String>,
+    pub s3_bucket: Option<String>,
+
+    #[confik(default = "us-east-1".to_string())]
+    pub s3_region: String,
+
+    pub s3_access_key: Option<String>,
+    pub s3_secret_key: Option<String>,
+
+    /// 업로드 후 이 시간이 지나도록 어떤 글에서도 쓰이지 않으면 고아 미디어로 봅니다.
+    #[confik(default = 24_u32)]
+    pub orphan_min_age_hours: u32,
+
+    /// 고아 미디어 점검 주기. 0 이면 주기 작업을 돌리지 않습니다.
+    #[confik(default = 0_u32)]
+    pub orphan_cleanup_interval_hours: u32,
+
+    /// false 면 주기 작업은 고아 미디어를 로그로만 보고합니다.
+    #[confik(default = false)]
+    pub orphan_cleanup_delete: bool,
+}
+
+#[derive(Debug, Default, Configuration, Clone)]
+pub struct ImageConfig {
+    /// 반응형 이미지로 생성할 가로 폭 목록 (쉼표 구분)
+    #[confik(default = "320,640,960,1280".to_string())]
+    pub variant_widths: String,
+
+    /// WebP 변환본은 항상 만들고, 켜면 AVIF 변환본도 함께 만듭니다.
+    #[confik(default = true)]
+    pub avif: bool,
+
+    #[confik(default = 80_u8)]
+    pub webp_quality: u8,
+
+    #[confik(default = 70_u8)]
+    pub avif_quality: u8,
+
+    /// `/img/{hash}` 리사이즈 URL 서명 키
+    #[confik(default = "change-me-in-production".to_string())]
+    pub proxy_secret: String,
+
+    #[confik(default = 2048_u32)]
+    pub proxy_max_dimension: u32,
+
+    #[confik(default = "./image-cache".to_string())]
+    pub cache_dir: String,
+
+    #[confik(default = 536_870_912_u64)]
+    pub cache_max_bytes: u64,
+}
+
+impl ImageConfig {
+    pub fn widths(&self) -> Vec<u32> {
+        let mut widths: Vec<u32> = self.variant_widths
+            .split(',')
+            .filter_map(|w| w.trim().parse().ok())
+            .filter(|w| *w > 0)
+            .collect();
+        widths.sort_unstable();
+        widths.dedup();
+        widths
+    }
+}
+
+#[derive(Debug, Default, Configuration, Clone)]
+pub struct CommentConfig {
+    /// true 면 새 댓글은 관리자 승인 후에 노출됩니다.
+    #[confik(default = false)]
+    pub require_approval: bool,
+
+    /// 댓글 폼 토큰 서명 키
+    #[confik(default = "change-me-in-production".to_string())]
+    pub form_secret: String,
+
+    /// 폼 토큰 발급 후 이 시간보다 빨리 제출하면 스팸으로 의심합니다.
+    #[confik(default = 3_u64)]
+    pub min_submit_secs: u64,
+
+    #[confik(default = 86_400_u64)]
+    pub form_token_ttl_secs: u64,
+
+    /// IP 당 `rate_limit_window_secs` 동안 허용하는 댓글 수. 0 이면 제한하지 않습니다.
+    #[confik(default = 5_usize)]
+    pub rate_limit_count: usize,
+
+    #[confik(default = 600_u64)]
+    pub rate_limit_window_secs: u64,
+
+    /// 같은 IP 가 한 댓글에 `password_attempt_window_secs` 동안 비밀번호를 넣어볼 수 있는 횟수. 0 이면 제한하지 않습니다.
+    #[confik(default = 5_usize)]
+    pub password_attempt_limit: usize,
+
+    #[confik(default = 900_u64)]
+    pub password_attempt_window_secs: u64,
+
+    /// 본문에 링크가 이보다 많으면 스팸으로 의심합니다.
+    #[confik(default = 2_usize)]
+    pub max_links: usize,
+
+    /// 금칙어 목록 (쉼표 구분, 대소문자 무시)
+    #[confik(default = String::new())]
+    pub banned_words: String,
+}
+
+impl CommentConfig {
+    pub fn banned_words(&self) -> Vec<String> {
+        self.banned_words
+            .split(',')
+            .map(|w| w.trim().to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Configuration, Clone)]
+pub struct ReactionConfig {
+    /// 글에 남길 수 있는 반응 (쉼표 구분). 기존 좋아요는 마이그레이션에서 👍 로 옮겨집니다.
+    #[confik(default = "👍,❤️,🎉,🤔".to_string())]
+    pub emojis: String,
+}
+
+impl ReactionConfig {
+    pub fn emojis(&self) -> Vec<String> {
+        let mut emojis: Vec<String> = Vec::new();
+        for emoji in self.emojis.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            if !emojis.iter().any(|e| e == emoji) {
+                emojis.push(emoji.to_string());
+            }
+        }
+        emojis
+    }
+}
+
+#[derive(Debug, Default, Configuration, Clone)]
+pub struct StatsConfig {
+    /// 기본 목록 외에 봇으로 볼 User-Agent 조각 (쉼표 구분, 대소문자 무시)
+    #[confik(default = String::new())]
+    pub bot_agents: String,
+
+    /// IP 당 분당 조회 요청이 이보다 많으면 봇으로 봅니다. 0 이면 보지 않습니다.
+    #[confik(default = 30_usize)]
+    pub bot_views_per_minute: usize,
+
+    /// true 면 조회수를 메모리에 모았다가 `view_flush_interval_secs` 마다 한 번에 반영합니다.
+    #[confik(default = true)]
+    pub buffer_views: bool,
+
+    #[confik(default = 5_u64)]
+    pub view_flush_interval_secs: u64,
+
+    /// 인기 급상승 점수가 절반으로 줄어드는 시간
+    #[confik(default = 48_u32)]
+    pub trending_half_life_hours: u32,
+
+    /// 인기 급상승 점수에 반영할 최근 일수
+    #[confik(default = 14_u32)]
+    pub trending_window_days: u32,
+
+    /// 블로그 자신의 호스트 (쉼표 구분). 여기서 넘어온 조회는 내부 이동으로 분류합니다.
+    #[confik(default = String::new())]
+    pub site_hosts: String,
+}
+
+impl StatsConfig {
+    pub fn site_hosts(&self) -> Vec<String> {
+        self.site_hosts
+            .split(',')
+            .map(|h| h.trim().to_lowercase())
+            .map(|h| h.strip_prefix("www.").map(str::to_string).unwrap_or(h))
+            .filter(|h| !h.is_empty())
+            .collect()
+    }
+
+    pub fn bot_agents(&self) -> Vec<String> {
+        self.bot_agents
+            .split(',')
+            .map(|a| a.trim().to_lowercase())
+            .filter(|a| !a.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Local,
+    S3,
+}
+
+impl confik::Configuration for StorageBackend {
+    type Builder = Option<Self>;
 }
 
 #[derive(Debug, Deserialize)]
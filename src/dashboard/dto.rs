@@ -0,0 +1,57 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::blog::model::PostSummary;
+use crate::comment::dto::CommentListResponse;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostCounts {
+    pub total: i64,
+    pub published: i64,
+    pub draft: i64,
+    /// 최근 7일, 30일 동안 쓴 글
+    pub last_7_days: i64,
+    pub last_30_days: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentCounts {
+    pub pending: i64,
+    pub approved: i64,
+    pub rejected: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrafficSummary {
+    pub days: i32,
+    pub views: i64,
+    pub visitors: i64,
+    pub reactions: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagUsage {
+    pub tag: String,
+    pub posts: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StorageUsage {
+    pub files: i64,
+    pub bytes: i64,
+    /// 어떤 글에서도 쓰이지 않는 파일
+    pub unused_files: i64,
+    pub unused_bytes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DashboardSummary {
+    pub posts: PostCounts,
+    pub comments: CommentCounts,
+    /// 최근 7일, 30일
+    pub traffic: Vec<TrafficSummary>,
+    /// 최근 30일 기준
+    pub top_posts: Vec<PostSummary>,
+    pub pending_comments: CommentListResponse,
+    pub tags: Vec<TagUsage>,
+    pub storage: StorageUsage,
+}
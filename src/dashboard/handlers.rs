@@ -0,0 +1,13 @@
+use actix_web::{ get, web, HttpResponse, Responder, ResponseError };
+
+use crate::dashboard::service;
+use crate::db::DbPool;
+use crate::user::handlers::Admin;
+
+#[get("/admin/dashboard")]
+pub async fn dashboard(_: Admin, pool: web::Data<DbPool>) -> impl Responder {
+    match service::summary(&pool).await {
+        Ok(summary) => { HttpResponse::Ok().json(summary) }
+        Err(e) => { e.error_response() }
+    }
+}
@@ -0,0 +1,6 @@
+use actix_web::web;
+use crate::dashboard::handlers;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(handlers::dashboard);
+}
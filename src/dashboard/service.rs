@@ -0,0 +1,119 @@
+use futures_util::try_join;
+
+use crate::blog::dto::PopularPeriod;
+use crate::blog::service as blog_service;
+use crate::comment::model::CommentStatus;
+use crate::comment::service as comment_service;
+use crate::dashboard::dto::{ CommentCounts, DashboardSummary, PostCounts, StorageUsage, TagUsage, TrafficSummary };
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+
+const TOP_POSTS: i64 = 5;
+const PENDING_COMMENTS: i64 = 10;
+
+/// 각 항목은 커넥션을 따로 받아 동시에 조회합니다.
+pub async fn summary(pool: &DbPool) -> Result<DashboardSummary, ServiceError> {
+    let (posts, comments, week, month, top_posts, pending_comments, tags, storage) = try_join!(
+        post_counts(pool),
+        comment_counts(pool),
+        traffic(pool, 7),
+        traffic(pool, 30),
+        blog_service::get_popular(pool, PopularPeriod::Month, TOP_POSTS),
+        comment_service::list_by_status(pool, CommentStatus::Pending, PENDING_COMMENTS, 0),
+        tag_usage(pool),
+        storage_usage(pool)
+    )?;
+
+    Ok(DashboardSummary {
+        posts,
+        comments,
+        traffic: vec![week, month],
+        top_posts,
+        pending_comments,
+        tags,
+        storage,
+    })
+}
+
+async fn post_counts(pool: &DbPool) -> Result<PostCounts, ServiceError> {
+    let client = pool.get().await?;
+
+    let row = client
+        .query_one(
+            "SELECT COUNT(*),
+                    COUNT(*) FILTER (WHERE status = 'published'),
+                    COUNT(*) FILTER (WHERE status = 'draft'),
+                    COUNT(*) FILTER (WHERE created_at >= NOW() - INTERVAL '7 days'),
+                    COUNT(*) FILTER (WHERE created_at >= NOW() - INTERVAL '30 days')
+             FROM posts",
+            &[]
+        ).await?;
+
+    Ok(PostCounts {
+        total: row.get(0),
+        published: row.get(1),
+        draft: row.get(2),
+        last_7_days: row.get(3),
+        last_30_days: row.get(4),
+    })
+}
+
+async fn comment_counts(pool: &DbPool) -> Result<CommentCounts, ServiceError> {
+    let client = pool.get().await?;
+
+    let row = client
+        .query_one(
+            "SELECT COUNT(*) FILTER (WHERE status = 'pending'),
+                    COUNT(*) FILTER (WHERE status = 'approved'),
+                    COUNT(*) FILTER (WHERE status = 'rejected')
+             FROM comments",
+            &[]
+        ).await?;
+
+    Ok(CommentCounts { pending: row.get(0), approved: row.get(1), rejected: row.get(2) })
+}
+
+async fn traffic(pool: &DbPool, days: i32) -> Result<TrafficSummary, ServiceError> {
+    let client = pool.get().await?;
+
+    let row = client
+        .query_one(
+            "SELECT COALESCE(SUM(views), 0)::bigint, COALESCE(SUM(visitors), 0)::bigint, COALESCE(SUM(reactions), 0)::bigint
+             FROM post_daily_stats
+             WHERE day > CURRENT_DATE - $1::int",
+            &[&days]
+        ).await?;
+
+    Ok(TrafficSummary { days, views: row.get(0), visitors: row.get(1), reactions: row.get(2) })
+}
+
+async fn tag_usage(pool: &DbPool) -> Result<Vec<TagUsage>, ServiceError> {
+    let client = pool.get().await?;
+
+    let rows = client
+        .query(
+            "SELECT tag, COUNT(*) AS posts FROM posts, unnest(tags) AS tag
+             GROUP BY tag
+             ORDER BY posts DESC, tag",
+            &[]
+        ).await?;
+
+    Ok(rows.into_iter().map(|row| TagUsage { tag: row.get(0), posts: row.get(1) }).collect())
+}
+
+async fn storage_usage(pool: &DbPool) -> Result<StorageUsage, ServiceError> {
+    let client = pool.get().await?;
+
+    let row = client
+        .query_one(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(size), 0)::bigint,
+                    COUNT(*) FILTER (WHERE u.media_id IS NULL),
+                    COALESCE(SUM(size) FILTER (WHERE u.media_id IS NULL), 0)::bigint
+             FROM media m
+             LEFT JOIN (SELECT DISTINCT media_id FROM media_usages) u ON u.media_id = m.id",
+            &[]
+        ).await?;
+
+    Ok(StorageUsage { files: row.get(0), bytes: row.get(1), unused_files: row.get(2), unused_bytes: row.get(3) })
+}
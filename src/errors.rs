@@ -1,6 +1,7 @@
 // src/errors.rs
 
-use actix_web::{ http::StatusCode, HttpResponse, ResponseError };
+use actix_multipart::MultipartError;
+use actix_web::{ error::BlockingError, http::StatusCode, HttpResponse, ResponseError };
 use deadpool_postgres::PoolError;
 use derive_more::Display;
 use serde::Serialize;
@@ -13,12 +14,19 @@ pub enum ServiceError {
     #[display("권한이 없습니다")]
     Unauthorized,
 
+    #[display("허용되지 않은 요청: {}", _0)] Forbidden(String),
+
     #[display("찾을 수 없습니다")]
     NotFound,
 
+    #[display("요청이 너무 많습니다. 잠시 후 다시 시도해주세요")]
+    TooManyRequests,
+
     #[display("서버 내부 오류")] InternalServerError(String),
 }
 
+impl std::error::Error for ServiceError {}
+
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
@@ -42,12 +50,32 @@ impl From<PoolError> for ServiceError {
     }
 }
 
+impl From<std::io::Error> for ServiceError {
+    fn from(e: std::io::Error) -> Self {
+        ServiceError::InternalServerError(e.to_string())
+    }
+}
+
+impl From<BlockingError> for ServiceError {
+    fn from(e: BlockingError) -> Self {
+        ServiceError::InternalServerError(e.to_string())
+    }
+}
+
+impl From<MultipartError> for ServiceError {
+    fn from(e: MultipartError) -> Self {
+        ServiceError::BadRequest(e.to_string())
+    }
+}
+
 impl ResponseError for ServiceError {
     fn status_code(&self) -> StatusCode {
         match *self {
             ServiceError::BadRequest(_) => StatusCode::BAD_REQUEST,
             ServiceError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ServiceError::Forbidden(_) => StatusCode::FORBIDDEN,
             ServiceError::NotFound => StatusCode::NOT_FOUND,
+            ServiceError::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
             ServiceError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
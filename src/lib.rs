@@ -1,6 +1,15 @@
 pub mod db;
 pub mod config;
 pub mod errors;
+pub mod visitor;
+pub mod slug;
 pub mod user;
 
 pub mod blog;
+pub mod media;
+pub mod comment;
+pub mod stats;
+pub mod dashboard;
+pub mod series;
+pub mod category;
+pub mod tag;
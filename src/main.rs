@@ -9,7 +9,16 @@ mod config;
 mod db;
 mod user;
 mod blog;
+mod media;
+mod comment;
+mod stats;
+mod dashboard;
+mod series;
+mod category;
+mod tag;
 mod errors;
+mod visitor;
+mod slug;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -22,16 +31,61 @@ async fn main() -> std::io::Result<()> {
         .try_build()
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
     let pool = db::init_pool(&config.pg);
+    let storage = media::storage
+        ::init_storage(&config.storage)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let image_cache = web::Data::new(
+        media::cache::DiskCache::open(&config.image.cache_dir, config.image.cache_max_bytes)?
+    );
+    let spam_filter = web::Data::new(comment::spam::SpamFilter::new(&config.comment));
+    let visitor_hasher = web::Data::new(visitor::VisitorHasher::new());
+    let bot_detector = web::Data::new(stats::bot::BotDetector::new(&config.stats));
+    let view_buffer = web::Data::new(stats::views::ViewBuffer::new(&config.stats));
+    let related_posts = web::Data::new(blog::related::RelatedPosts::new());
     let bind_addr = config.server_addr.clone();
 
+    if config.storage.orphan_cleanup_interval_hours > 0 {
+        actix_web::rt::spawn(
+            media::service::run_orphan_cleanup(pool.clone(), storage.clone(), config.storage.clone())
+        );
+    }
+    if view_buffer.is_buffered() {
+        actix_web::rt::spawn(
+            stats::views::run_view_flush(view_buffer.clone(), pool.clone(), config.stats.clone())
+        );
+    }
+    let storage = web::Data::from(storage);
+    let shutdown_pool = pool.clone();
+    let shutdown_views = view_buffer.clone();
+
     let server = HttpServer::new(move || {
         App::new()
             .wrap(Logger::default())
             .app_data(web::Data::new(config.clone()))
             .app_data(web::Data::new(pool.clone()))
+            .app_data(storage.clone())
+            .app_data(image_cache.clone())
+            .app_data(spam_filter.clone())
+            .app_data(visitor_hasher.clone())
+            .app_data(bot_detector.clone())
+            .app_data(view_buffer.clone())
+            .app_data(related_posts.clone())
             .configure(user::routes::init)
             .configure(blog::routes::init)
+            .configure(media::routes::init)
+            .configure(comment::routes::init)
+            .configure(stats::routes::init)
+            .configure(series::routes::init)
+            .configure(category::routes::init)
+            .configure(tag::routes::init)
+            .configure(dashboard::routes::init)
     }).bind(&bind_addr)?;
     tracing::info!("server running at http://{bind_addr}");
-    server.run().await
+    server.run().await?;
+
+    // 종료 전에 남은 조회수를 반영합니다.
+    if let Err(e) = shutdown_views.flush(&shutdown_pool).await {
+        tracing::error!("종료 중 조회수 반영 실패: {e}");
+    }
+    Ok(())
 }
@@ -0,0 +1,132 @@
+use std::collections::{ BTreeMap, HashMap };
+use std::path::{ Path, PathBuf };
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::errors::ServiceError;
+
+/// 변환된 이미지를 디스크에 보관하는 LRU 캐시. 전체 크기가 `max_bytes` 를 넘으면
+/// 가장 오래 사용되지 않은 파일부터 지웁니다.
+pub struct DiskCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    index: Mutex<CacheIndex>,
+}
+
+#[derive(Default)]
+struct CacheIndex {
+    entries: HashMap<String, (u64, u64)>,
+    recency: BTreeMap<u64, String>,
+    total_bytes: u64,
+    tick: u64,
+}
+
+impl CacheIndex {
+    fn touch(&mut self, name: &str) -> bool {
+        self.tick += 1;
+        let tick = self.tick;
+        match self.entries.get_mut(name) {
+            Some((_, last_used)) => {
+                self.recency.remove(last_used);
+                *last_used = tick;
+                self.recency.insert(tick, name.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove(&mut self, name: &str) {
+        if let Some((size, last_used)) = self.entries.remove(name) {
+            self.recency.remove(&last_used);
+            self.total_bytes -= size;
+        }
+    }
+
+    fn insert(&mut self, name: &str, size: u64, max_bytes: u64) -> Vec<String> {
+        self.remove(name);
+        self.tick += 1;
+        self.entries.insert(name.to_string(), (size, self.tick));
+        self.recency.insert(self.tick, name.to_string());
+        self.total_bytes += size;
+
+        let mut evicted = Vec::new();
+        while self.total_bytes > max_bytes && self.entries.len() > 1 {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some((size, _)) = self.entries.remove(&oldest) {
+                self.total_bytes -= size;
+            }
+            evicted.push(oldest);
+        }
+        evicted
+    }
+}
+
+impl DiskCache {
+    /// 캐시 디렉터리를 만들고, 기존 파일은 수정 시각 순으로 인덱스에 올립니다.
+    pub fn open(dir: impl AsRef<Path>, max_bytes: u64) -> std::io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let meta = entry.metadata()?;
+            if !meta.is_file() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                if name.starts_with('.') {
+                    std::fs::remove_file(entry.path()).ok();
+                    continue;
+                }
+                let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.push((modified, name.to_string(), meta.len()));
+            }
+        }
+        files.sort();
+
+        let mut index = CacheIndex::default();
+        let mut evicted = Vec::new();
+        for (_, name, size) in files {
+            evicted.extend(index.insert(&name, size, max_bytes));
+        }
+        for name in evicted {
+            std::fs::remove_file(dir.join(name)).ok();
+        }
+
+        Ok(DiskCache { dir, max_bytes, index: Mutex::new(index) })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheIndex> {
+        self.index.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub async fn get(&self, name: &str) -> Option<Vec<u8>> {
+        if !self.lock().touch(name) {
+            return None;
+        }
+
+        match tokio::fs::read(self.dir.join(name)).await {
+            Ok(bytes) => Some(bytes),
+            Err(_) => {
+                self.lock().remove(name);
+                None
+            }
+        }
+    }
+
+    pub async fn put(&self, name: &str, bytes: &[u8]) -> Result<(), ServiceError> {
+        let tmp = self.dir.join(format!(".{}.tmp", name));
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, self.dir.join(name)).await?;
+
+        let evicted = self.lock().insert(name, bytes.len() as u64, self.max_bytes);
+        for name in evicted {
+            tokio::fs::remove_file(self.dir.join(name)).await.ok();
+        }
+        Ok(())
+    }
+}
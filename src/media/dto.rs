@@ -0,0 +1,59 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::media::model::Media;
+use crate::media::proxy::{ Fit, OutputFormat, ResizeParams };
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MediaListResponse {
+    pub total_count: i64,
+    pub media: Vec<Media>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrphanCleanupRequest {
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+    pub min_age_hours: Option<u32>,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrphanReport {
+    pub dry_run: bool,
+    pub orphans: Vec<Media>,
+    pub deleted: usize,
+    pub freed_bytes: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResizeQuery {
+    pub w: Option<u32>,
+    pub h: Option<u32>,
+    #[serde(default)]
+    pub fit: Fit,
+    #[serde(default)]
+    pub fmt: OutputFormat,
+    #[serde(default)]
+    pub sig: String,
+}
+
+impl ResizeQuery {
+    pub fn into_parts(self) -> (ResizeParams, String) {
+        (ResizeParams { w: self.w, h: self.h, fit: self.fit, fmt: self.fmt }, self.sig)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignImageRequest {
+    pub key: String,
+    #[serde(flatten)]
+    pub params: ResizeParams,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignImageResponse {
+    pub url: String,
+}
@@ -0,0 +1,203 @@
+use actix_multipart::Multipart;
+use actix_web::{ delete, get, post, web, HttpMessage, HttpRequest, HttpResponse, Responder, ResponseError };
+use actix_web::http::header::{ CacheControl, CacheDirective, ETag, EntityTag, IfNoneMatch };
+use futures_util::StreamExt;
+use serde::Deserialize;
+
+use crate::config::AppConfig;
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::media::cache::DiskCache;
+use crate::media::dto::{ OrphanCleanupRequest, ResizeQuery, SignImageRequest, SignImageResponse };
+use crate::media::{ proxy, service };
+use crate::media::storage::Storage;
+use crate::user::handlers::Admin;
+
+#[derive(Debug, Deserialize)]
+struct MediaQuery {
+    q: Option<String>,
+    page: Option<u32>,
+    #[serde(rename = "pageSize")]
+    page_size: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeleteMediaQuery {
+    #[serde(default)]
+    force: bool,
+}
+
+async fn read_file_field(mut payload: Multipart, max_bytes: usize) -> Result<(String, Vec<u8>), ServiceError> {
+    while let Some(field) = payload.next().await {
+        let mut field = field?;
+        if field.name() != Some("file") {
+            continue;
+        }
+        let filename = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .unwrap_or_default()
+            .to_string();
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk?;
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(
+                    ServiceError::BadRequest(format!("파일 크기는 {} 바이트를 넘을 수 없습니다", max_bytes))
+                );
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok((filename, bytes));
+    }
+
+    Err(ServiceError::BadRequest("file 필드가 필요합니다".into()))
+}
+
+#[post("/media")]
+pub async fn upload_media(
+    _: Admin,
+    cfg: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    storage: web::Data<dyn Storage>,
+    payload: Multipart
+) -> impl Responder {
+    let (filename, bytes) = match read_file_field(payload, cfg.storage.max_upload_bytes).await {
+        Ok(field) => field,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    match service::upload(&pool, storage.get_ref(), &cfg.image, &filename, bytes).await {
+        Ok(media) => { HttpResponse::Created().json(media) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/media")]
+pub async fn list_media(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<MediaQuery>
+) -> impl Responder {
+    let page_size = query.page_size.unwrap_or(24).max(1) as i64;
+    let offset = ((query.page.unwrap_or(1).max(1) as i64) - 1) * page_size;
+    let q = query.q.as_deref().filter(|q| !q.trim().is_empty());
+
+    match service::list(&pool, q, page_size, offset).await {
+        Ok(data) => { HttpResponse::Ok().json(data) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[delete("/media/{id}")]
+pub async fn delete_media(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    storage: web::Data<dyn Storage>,
+    path: web::Path<i32>,
+    web::Query(query): web::Query<DeleteMediaQuery>
+) -> impl Responder {
+    let id = path.into_inner();
+    match service::delete(&pool, storage.get_ref(), id, query.force).await {
+        Ok(_) => { HttpResponse::NoContent().finish() }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/media/orphans/cleanup")]
+pub async fn cleanup_orphans(
+    _: Admin,
+    cfg: web::Data<AppConfig>,
+    pool: web::Data<DbPool>,
+    storage: web::Data<dyn Storage>,
+    web::Json(dto): web::Json<OrphanCleanupRequest>
+) -> impl Responder {
+    let min_age_hours = dto.min_age_hours.unwrap_or(cfg.storage.orphan_min_age_hours);
+    match service::cleanup_orphans(&pool, storage.get_ref(), min_age_hours, dto.dry_run).await {
+        Ok(report) => { HttpResponse::Ok().json(report) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/media/files/{key:.*}")]
+pub async fn serve_media(storage: web::Data<dyn Storage>, path: web::Path<String>) -> impl Responder {
+    let key = path.into_inner();
+
+    match storage.get(&key).await {
+        Ok(bytes) => {
+            HttpResponse::Ok()
+                .content_type(service::content_type_for_key(&key))
+                .insert_header(
+                    CacheControl(vec![CacheDirective::Public, CacheDirective::MaxAge(31_536_000)])
+                )
+                .body(bytes)
+        }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/img/{hash}")]
+pub async fn resize_image(
+    req: HttpRequest,
+    cfg: web::Data<AppConfig>,
+    storage: web::Data<dyn Storage>,
+    cache: web::Data<DiskCache>,
+    path: web::Path<String>,
+    web::Query(query): web::Query<ResizeQuery>
+) -> impl Responder {
+    let key = path.into_inner();
+    let (params, sig) = query.into_parts();
+
+    let request = match service::resize_request(&cfg.image, &key, params, &sig) {
+        Ok(request) => request,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    // 서명된 파라미터만으로 ETag 가 정해지므로 캐시나 디코더를 거치지 않고 304 를 돌려줍니다.
+    let etag = EntityTag::new_strong(request.etag.clone());
+    let cache_control = CacheControl(
+        vec![CacheDirective::Public, CacheDirective::MaxAge(31_536_000), CacheDirective::Extension("immutable".into(), None)]
+    );
+
+    let not_modified = match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => false,
+    };
+    if not_modified {
+        return HttpResponse::NotModified()
+            .insert_header(ETag(etag))
+            .insert_header(cache_control)
+            .finish();
+    }
+
+    let content_type = request.content_type;
+    match service::resized_image(storage.get_ref(), &cache, &cfg.image, request).await {
+        Ok(bytes) => {
+            HttpResponse::Ok()
+                .content_type(content_type)
+                .insert_header(ETag(etag))
+                .insert_header(cache_control)
+                .body(bytes)
+        }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/img/sign")]
+pub async fn sign_image(
+    _: Admin,
+    cfg: web::Data<AppConfig>,
+    web::Json(dto): web::Json<SignImageRequest>
+) -> impl Responder {
+    if let Err(e) = dto.params.validate(cfg.image.proxy_max_dimension) {
+        return e.error_response();
+    }
+    let url = proxy::signed_url(&cfg.image.proxy_secret, &dto.key, &dto.params);
+    HttpResponse::Ok().json(SignImageResponse { url })
+}
@@ -0,0 +1,8 @@
+pub mod model;
+pub mod dto;
+pub mod storage;
+pub mod cache;
+pub mod proxy;
+pub mod service;
+pub mod handlers;
+pub mod routes;
@@ -0,0 +1,47 @@
+use std::error::Error;
+
+use chrono::NaiveDateTime;
+use serde::{ Deserialize, Serialize };
+use tokio_pg_mapper_derive::PostgresMapper;
+use tokio_postgres::types::{ FromSql, Json, Type };
+
+#[derive(Debug, Serialize, Deserialize, PostgresMapper)]
+#[pg_mapper(table = "media")]
+pub struct Media {
+    pub id: i32,
+    pub key: String,
+    pub hash: String,
+    pub url: String,
+    pub filename: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size: i64,
+    pub placeholder: String,
+    pub variants: ImageVariants,
+    pub usage_count: i64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageVariant {
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+    pub content_type: String,
+}
+
+/// `srcset` 구성을 위한 변환 이미지 목록. DB 에는 JSONB 배열로 저장됩니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ImageVariants(pub Vec<ImageVariant>);
+
+impl<'a> FromSql<'a> for ImageVariants {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Json::<Vec<ImageVariant>>::from_sql(ty, raw).map(|Json(variants)| ImageVariants(variants))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Json<Vec<ImageVariant>> as FromSql>::accepts(ty)
+    }
+}
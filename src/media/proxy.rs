@@ -0,0 +1,181 @@
+use std::io::Cursor;
+
+use hmac::{ Hmac, Mac };
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use image::DynamicImage;
+use serde::{ Deserialize, Serialize };
+use sha2::{ Digest, Sha256 };
+
+use crate::config::ImageConfig;
+use crate::errors::ServiceError;
+use crate::media::service::{ decode, encode_webp, AVIF_SPEED };
+
+type HmacSha256 = Hmac<Sha256>;
+
+const JPEG_QUALITY: u8 = 82;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fit {
+    /// 비율을 유지하며 상자 안에 맞춥니다.
+    #[default]
+    Contain,
+    /// 비율을 유지하며 상자를 채우고 넘치는 부분은 잘라냅니다.
+    Cover,
+    /// 비율을 무시하고 정확히 맞춥니다.
+    Fill,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Webp,
+    Avif,
+    Jpeg,
+    Png,
+}
+
+impl OutputFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Webp => "webp",
+            OutputFormat::Avif => "avif",
+            OutputFormat::Jpeg => "jpeg",
+            OutputFormat::Png => "png",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            OutputFormat::Webp => "image/webp",
+            OutputFormat::Avif => "image/avif",
+            OutputFormat::Jpeg => "image/jpeg",
+            OutputFormat::Png => "image/png",
+        }
+    }
+}
+
+impl Fit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Fit::Contain => "contain",
+            Fit::Cover => "cover",
+            Fit::Fill => "fill",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResizeParams {
+    pub w: Option<u32>,
+    pub h: Option<u32>,
+    #[serde(default)]
+    pub fit: Fit,
+    #[serde(default)]
+    pub fmt: OutputFormat,
+}
+
+impl ResizeParams {
+    pub fn validate(&self, max_dimension: u32) -> Result<(), ServiceError> {
+        for value in [self.w, self.h].into_iter().flatten() {
+            if value == 0 || value > max_dimension {
+                return Err(
+                    ServiceError::BadRequest(format!("w, h 는 1 이상 {} 이하여야 합니다", max_dimension))
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn query(&self) -> String {
+        format!(
+            "w={}&h={}&fit={}&fmt={}",
+            self.w.map(|w| w.to_string()).unwrap_or_default(),
+            self.h.map(|h| h.to_string()).unwrap_or_default(),
+            self.fit.as_str(),
+            self.fmt.as_str()
+        )
+    }
+
+    fn canonical(&self, key: &str) -> String {
+        format!("{}?{}", key, self.query())
+    }
+}
+
+fn mac(secret: &str, key: &str, params: &ResizeParams) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(params.canonical(key).as_bytes());
+    mac
+}
+
+pub fn sign(secret: &str, key: &str, params: &ResizeParams) -> String {
+    hex::encode(mac(secret, key, params).finalize().into_bytes())
+}
+
+pub fn verify(secret: &str, key: &str, params: &ResizeParams, sig: &str) -> bool {
+    match hex::decode(sig) {
+        Ok(sig) => mac(secret, key, params).verify_slice(&sig).is_ok(),
+        Err(_) => false,
+    }
+}
+
+pub fn signed_url(secret: &str, key: &str, params: &ResizeParams) -> String {
+    format!("/img/{}?{}&sig={}", key, params.query(), sign(secret, key, params))
+}
+
+/// 같은 원본과 파라미터는 항상 같은 이름을 갖습니다. ETag 로도 사용합니다.
+pub fn cache_name(key: &str, params: &ResizeParams) -> String {
+    let digest = hex::encode(Sha256::digest(params.canonical(key).as_bytes()));
+    format!("{}.{}", &digest[..40], params.fmt.as_str())
+}
+
+fn resize(img: DynamicImage, params: &ResizeParams) -> DynamicImage {
+    let (orig_w, orig_h) = (img.width(), img.height());
+
+    match (params.w, params.h, params.fit) {
+        (None, None, _) => img,
+        (Some(w), Some(h), Fit::Cover) => img.resize_to_fill(w, h, FilterType::Lanczos3),
+        (Some(w), Some(h), Fit::Fill) => img.resize_exact(w, h, FilterType::Lanczos3),
+        (w, h, _) => {
+            let w = w.unwrap_or(u32::MAX).min(orig_w);
+            let h = h.unwrap_or(u32::MAX).min(orig_h);
+            if w == orig_w && h == orig_h {
+                img
+            } else {
+                img.resize(w, h, FilterType::Lanczos3)
+            }
+        }
+    }
+}
+
+pub fn render(bytes: &[u8], params: &ResizeParams, image_cfg: &ImageConfig) -> Result<Vec<u8>, ServiceError> {
+    let (_, img) = decode(bytes)?;
+    let img = resize(img, params);
+
+    let img = if img.color().has_alpha() && params.fmt != OutputFormat::Jpeg {
+        DynamicImage::ImageRgba8(img.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(img.to_rgb8())
+    };
+
+    let mut buf = Cursor::new(Vec::new());
+    let result = match params.fmt {
+        OutputFormat::Webp => {
+            buf.get_mut().extend(encode_webp(&img, image_cfg.webp_quality));
+            Ok(())
+        }
+        OutputFormat::Avif =>
+            img.write_with_encoder(
+                AvifEncoder::new_with_speed_quality(&mut buf, AVIF_SPEED, image_cfg.avif_quality)
+            ),
+        OutputFormat::Jpeg => img.write_with_encoder(JpegEncoder::new_with_quality(&mut buf, JPEG_QUALITY)),
+        OutputFormat::Png => img.write_with_encoder(PngEncoder::new(&mut buf)),
+    };
+    result.map_err(|e| ServiceError::InternalServerError(e.to_string()))?;
+
+    Ok(buf.into_inner())
+}
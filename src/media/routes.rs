@@ -0,0 +1,12 @@
+use actix_web::web;
+use crate::media::handlers;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(handlers::upload_media)
+        .service(handlers::list_media)
+        .service(handlers::delete_media)
+        .service(handlers::cleanup_orphans)
+        .service(handlers::serve_media)
+        .service(handlers::sign_image)
+        .service(handlers::resize_image);
+}
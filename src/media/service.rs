@@ -0,0 +1,675 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::web;
+use deadpool_postgres::GenericClient;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use image::codecs::avif::AvifEncoder;
+use image::codecs::gif::{ GifDecoder, GifEncoder, Repeat };
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{ imageops::blur, AnimationDecoder, DynamicImage, ImageDecoder, ImageError, ImageFormat, ImageReader };
+use reqwest::Client;
+use sha2::{ Digest, Sha256 };
+use tokio_pg_mapper::FromTokioPostgresRow;
+use tokio_postgres::types::Json;
+
+use crate::config::{ ImageConfig, StorageConfig };
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::media::cache::DiskCache;
+use crate::media::dto::{ MediaListResponse, OrphanReport };
+use crate::media::model::{ ImageVariant, ImageVariants, Media };
+use crate::media::proxy::{ self, ResizeParams };
+use crate::media::storage::Storage;
+
+const PLACEHOLDER_SIZE: u32 = 160;
+const ORIGINAL_JPEG_QUALITY: u8 = 90;
+pub const AVIF_SPEED: u8 = 8;
+
+const UPLOAD_FORMATS: [ImageFormat; 4] = [ImageFormat::Jpeg, ImageFormat::Png, ImageFormat::WebP, ImageFormat::Gif];
+
+pub fn blur_data_url(img: &DynamicImage) -> Result<String, ServiceError> {
+    let blurred = blur(img, 10.0);
+
+    let mut buf = Vec::new();
+    let mut encoder = JpegEncoder::new_with_quality(&mut buf, 60);
+    encoder.encode_image(&blurred).map_err(|e| ServiceError::InternalServerError(e.to_string()))?;
+
+    let b64 = STANDARD.encode(&buf);
+    Ok(format!("data:image/jpeg;base64,{}", b64))
+}
+
+pub fn content_hash(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+pub fn content_type_for_key(key: &str) -> &'static str {
+    ImageFormat::from_path(key)
+        .map(|format| format.to_mime_type())
+        .unwrap_or("application/octet-stream")
+}
+
+pub async fn fetch_remote(url: &str) -> Result<Vec<u8>, ServiceError> {
+    let response = Client::new()
+        .get(url)
+        .send().await
+        .map_err(|e| ServiceError::BadRequest(format!("이미지 다운로드 실패: {}", e)))?;
+    let bytes = response
+        .bytes().await
+        .map_err(|e| ServiceError::BadRequest(format!("이미지 바이트 읽기 실패: {}", e)))?;
+    Ok(bytes.to_vec())
+}
+
+/// 우리 스토리지에 있는 이미지는 직접 읽고, 그 외 URL 은 내려받습니다.
+pub async fn load_source(storage: &dyn Storage, url: &str) -> Result<Vec<u8>, ServiceError> {
+    match storage.key_from_url(url) {
+        Some(key) => storage.get(&key).await,
+        None => fetch_remote(url).await,
+    }
+}
+
+fn decode_error(e: ImageError) -> ServiceError {
+    match e {
+        ImageError::Unsupported(e) => ServiceError::BadRequest(format!("지원하지 않는 이미지 형식입니다: {}", e)),
+        e => ServiceError::BadRequest(format!("손상되었거나 읽을 수 없는 이미지입니다: {}", e)),
+    }
+}
+
+/// 이미지를 디코딩하고 EXIF Orientation 에 맞게 회전합니다.
+pub fn decode(bytes: &[u8]) -> Result<(ImageFormat, DynamicImage), ServiceError> {
+    let reader = ImageReader::new(Cursor::new(bytes)).with_guessed_format()?;
+    let format = reader
+        .format()
+        .ok_or_else(|| ServiceError::BadRequest("지원하지 않는 이미지 형식입니다".into()))?;
+
+    let mut decoder = reader.into_decoder().map_err(decode_error)?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut img = DynamicImage::from_decoder(decoder).map_err(decode_error)?;
+    img.apply_orientation(orientation);
+
+    Ok((format, img))
+}
+
+/// `image` 의 WebP 인코더는 무손실만 지원해 원본보다 커지기 쉬우므로 libwebp 로 손실 압축합니다.
+pub fn encode_webp(img: &DynamicImage, quality: u8) -> Vec<u8> {
+    let (width, height) = (img.width(), img.height());
+    let encoded = if img.color().has_alpha() {
+        let rgba = img.to_rgba8();
+        webp::Encoder::from_rgba(&rgba, width, height).encode(f32::from(quality))
+    } else {
+        let rgb = img.to_rgb8();
+        webp::Encoder::from_rgb(&rgb, width, height).encode(f32::from(quality))
+    };
+    encoded.to_vec()
+}
+
+fn orientation(bytes: &[u8]) -> Orientation {
+    ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()
+        .and_then(|reader| reader.into_decoder().ok())
+        .and_then(|mut decoder| decoder.orientation().ok())
+        .unwrap_or(Orientation::NoTransforms)
+}
+
+/// WebP(RIFF) 컨테이너에서 EXIF, XMP 청크를 빼고 VP8X 플래그를 맞춥니다. 형식이 어긋나면 `None` 입니다.
+fn strip_webp_chunks(bytes: &[u8]) -> Option<Vec<u8>> {
+    const EXIF_FLAG: u8 = 0x08;
+    const XMP_FLAG: u8 = 0x04;
+
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WEBP" {
+        return None;
+    }
+    let mut out = b"RIFF\0\0\0\0WEBP".to_vec();
+    let mut pos = 12;
+    while pos < bytes.len() {
+        let header = bytes.get(pos..pos + 8)?;
+        let size = u32::from_le_bytes(header[4..8].try_into().ok()?) as usize;
+        let end = pos.checked_add(8 + size + size % 2)?;
+        let chunk = bytes.get(pos..end.min(bytes.len()))?;
+        match &header[0..4] {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" => {
+                let mut chunk = chunk.to_vec();
+                *chunk.get_mut(8)? &= !(EXIF_FLAG | XMP_FLAG);
+                out.extend_from_slice(&chunk);
+            }
+            _ => out.extend_from_slice(chunk),
+        }
+        pos = end;
+    }
+    let riff_size = u32::try_from(out.len() - 8).ok()?;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(out)
+}
+
+/// 원본을 다시 인코딩해 EXIF(GPS 포함) 등 모든 메타데이터를 제거합니다. GIF 는 애니메이션을 유지합니다.
+fn strip_metadata(format: ImageFormat, bytes: &[u8], img: &DynamicImage) -> Result<Vec<u8>, ServiceError> {
+    let mut buf = Cursor::new(Vec::new());
+    let result = match format {
+        ImageFormat::Jpeg =>
+            DynamicImage::ImageRgb8(img.to_rgb8()).write_with_encoder(
+                JpegEncoder::new_with_quality(&mut buf, ORIGINAL_JPEG_QUALITY)
+            ),
+        ImageFormat::Png => img.write_with_encoder(PngEncoder::new(&mut buf)),
+        ImageFormat::WebP => {
+            // 다시 인코딩하면 화질이 떨어지거나(손실) 파일이 커지므로(무손실) 메타데이터 청크만 걷어냅니다.
+            // 회전이 필요한 드문 경우에만 무손실로 다시 인코딩합니다.
+            if orientation(bytes) == Orientation::NoTransforms
+                && let Some(stripped) = strip_webp_chunks(bytes)
+            {
+                return Ok(stripped);
+            }
+            let img = if img.color().has_alpha() {
+                DynamicImage::ImageRgba8(img.to_rgba8())
+            } else {
+                DynamicImage::ImageRgb8(img.to_rgb8())
+            };
+            img.write_with_encoder(WebPEncoder::new_lossless(&mut buf))
+        }
+        ImageFormat::Gif => {
+            let frames = GifDecoder::new(Cursor::new(bytes))
+                .and_then(|decoder| decoder.into_frames().collect_frames())
+                .map_err(decode_error)?;
+            let mut encoder = GifEncoder::new(&mut buf);
+            encoder
+                .set_repeat(Repeat::Infinite)
+                .and_then(|_| encoder.encode_frames(frames))
+        }
+        _ => {
+            return Err(ServiceError::BadRequest(format!("지원하지 않는 이미지 형식입니다: {:?}", format)));
+        }
+    };
+    result.map_err(|e| ServiceError::InternalServerError(e.to_string()))?;
+
+    Ok(buf.into_inner())
+}
+
+struct EncodedVariant {
+    key: String,
+    bytes: Vec<u8>,
+    variant: ImageVariant,
+}
+
+fn encode_variants(
+    hash: &str,
+    img: &DynamicImage,
+    image_cfg: &ImageConfig
+) -> Result<Vec<EncodedVariant>, ServiceError> {
+    let mut widths: Vec<u32> = image_cfg
+        .widths()
+        .into_iter()
+        .filter(|w| *w <= img.width())
+        .collect();
+    if widths.is_empty() {
+        widths.push(img.width());
+    }
+
+    let mut formats = vec![ImageFormat::WebP];
+    if image_cfg.avif {
+        formats.push(ImageFormat::Avif);
+    }
+
+    let mut encoded = Vec::new();
+    for format in formats {
+        for &width in &widths {
+            let resized = if width == img.width() {
+                img.clone()
+            } else {
+                img.resize(width, u32::MAX, FilterType::Lanczos3)
+            };
+            let resized = if resized.color().has_alpha() {
+                DynamicImage::ImageRgba8(resized.to_rgba8())
+            } else {
+                DynamicImage::ImageRgb8(resized.to_rgb8())
+            };
+
+            let bytes = match format {
+                ImageFormat::Avif => {
+                    let mut buf = Cursor::new(Vec::new());
+                    resized
+                        .write_with_encoder(
+                            AvifEncoder::new_with_speed_quality(&mut buf, AVIF_SPEED, image_cfg.avif_quality)
+                        )
+                        .map_err(|e| ServiceError::InternalServerError(e.to_string()))?;
+                    buf.into_inner()
+                }
+                _ => encode_webp(&resized, image_cfg.webp_quality),
+            };
+
+            let extension = format.extensions_str().first().copied().unwrap_or("bin");
+            encoded.push(EncodedVariant {
+                key: format!("{}/w{}.{}", hash, width, extension),
+                bytes,
+                variant: ImageVariant {
+                    url: String::new(),
+                    width: resized.width(),
+                    height: resized.height(),
+                    content_type: format.to_mime_type().to_string(),
+                },
+            });
+        }
+    }
+
+    Ok(encoded)
+}
+
+async fn store_variants(
+    storage: &dyn Storage,
+    encoded: Vec<EncodedVariant>
+) -> Result<ImageVariants, ServiceError> {
+    let mut variants = Vec::with_capacity(encoded.len());
+    for EncodedVariant { key, bytes, mut variant } in encoded {
+        storage.put(&key, bytes, &variant.content_type).await?;
+        variant.url = storage.public_url(&key);
+        variants.push(variant);
+    }
+    Ok(ImageVariants(variants))
+}
+
+struct DecodedUpload {
+    hash: String,
+    bytes: Vec<u8>,
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    placeholder: String,
+    variants: Vec<EncodedVariant>,
+}
+
+fn decode_upload(bytes: &[u8], image_cfg: &ImageConfig) -> Result<DecodedUpload, ServiceError> {
+    let (format, img) = decode(bytes)?;
+    if !UPLOAD_FORMATS.contains(&format) {
+        return Err(ServiceError::BadRequest(format!("지원하지 않는 이미지 형식입니다: {:?}", format)));
+    }
+
+    let bytes = strip_metadata(format, bytes, &img)?;
+    let hash = content_hash(&bytes);
+
+    let placeholder = blur_data_url(&img.thumbnail(PLACEHOLDER_SIZE, PLACEHOLDER_SIZE))?;
+    let variants = encode_variants(&hash, &img, image_cfg)?;
+
+    Ok(DecodedUpload {
+        hash,
+        bytes,
+        format,
+        width: img.width(),
+        height: img.height(),
+        placeholder,
+        variants,
+    })
+}
+
+const MEDIA_COLUMNS: &str =
+    "id, key, hash, url, filename, content_type, width, height, size, placeholder, variants, created_at,
+     (SELECT COUNT(*) FROM media_usages u WHERE u.media_id = media.id) AS usage_count";
+
+pub async fn upload(
+    pool: &DbPool,
+    storage: &dyn Storage,
+    image_cfg: &ImageConfig,
+    filename: &str,
+    bytes: Vec<u8>
+) -> Result<Media, ServiceError> {
+    if bytes.is_empty() {
+        return Err(ServiceError::BadRequest("업로드할 파일이 없습니다".into()));
+    }
+
+    let image_cfg = image_cfg.clone();
+    let decoded = web::block(move || decode_upload(&bytes, &image_cfg)).await??;
+
+    let extension = decoded.format.extensions_str().first().copied().unwrap_or("bin");
+    let key = format!("{}.{}", decoded.hash, extension);
+    let content_type = decoded.format.to_mime_type().to_string();
+    let size = decoded.bytes.len() as i64;
+
+    storage.put(&key, decoded.bytes, &content_type).await?;
+    let variants = store_variants(storage, decoded.variants).await?;
+
+    let client = pool.get().await?;
+
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "INSERT INTO media (key, hash, url, filename, content_type, width, height, size, placeholder, variants)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                 ON CONFLICT (key) DO UPDATE SET
+                    url = EXCLUDED.url,
+                    placeholder = EXCLUDED.placeholder,
+                    variants = EXCLUDED.variants
+                 RETURNING {}",
+                MEDIA_COLUMNS
+            )
+        ).await?;
+
+    let row = client
+        .query_one(
+            &stmt,
+            &[
+                &key,
+                &decoded.hash,
+                &storage.public_url(&key),
+                &filename,
+                &content_type,
+                &(decoded.width as i32),
+                &(decoded.height as i32),
+                &size,
+                &decoded.placeholder,
+                &Json(&variants.0),
+            ]
+        ).await?;
+
+    Ok(Media::from_row_ref(&row)?)
+}
+
+pub async fn list(
+    pool: &DbPool,
+    query: Option<&str>,
+    limit: i64,
+    offset: i64
+) -> Result<MediaListResponse, ServiceError> {
+    let client = pool.get().await?;
+    let pattern = query.map(|q| format!("%{}%", q.trim()));
+
+    let count_row = client
+        .query_one(
+            "SELECT COUNT(*) FROM media
+             WHERE $1::TEXT IS NULL OR filename ILIKE $1 OR key ILIKE $1",
+            &[&pattern]
+        ).await?;
+    let total_count: i64 = count_row.get(0);
+
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "SELECT {}
+                 FROM media
+                 WHERE $1::TEXT IS NULL OR filename ILIKE $1 OR key ILIKE $1
+                 ORDER BY created_at DESC, id DESC
+                 OFFSET $2
+                 LIMIT  $3",
+                MEDIA_COLUMNS
+            )
+        ).await?;
+    let rows = client.query(&stmt, &[&pattern, &offset, &limit]).await?;
+
+    let media = rows
+        .into_iter()
+        .map(|row| Media::from_row_ref(&row).map_err(ServiceError::from))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(MediaListResponse { total_count, media })
+}
+
+async fn remove_files(storage: &dyn Storage, media: &Media) -> Result<(), ServiceError> {
+    for variant in &media.variants.0 {
+        if let Some(key) = storage.key_from_url(&variant.url) {
+            storage.delete(&key).await?;
+        }
+    }
+    storage.delete(&media.key).await
+}
+
+/// 행을 지운 뒤에 파일을 지웁니다. 사용 여부 확인과 삭제를 한 문장으로 묶어,
+/// 그 사이에 이 미디어를 쓰는 글이 저장되면 지우지 않습니다.
+async fn delete_row(client: &impl GenericClient, media_id: i32, force: bool) -> Result<Option<Media>, ServiceError> {
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "DELETE FROM media
+                 WHERE id = $1 AND ($2 OR NOT EXISTS (SELECT 1 FROM media_usages WHERE media_id = $1))
+                 RETURNING {}",
+                MEDIA_COLUMNS
+            )
+        ).await?;
+    let row = client.query_opt(&stmt, &[&media_id, &force]).await?;
+    row.map(|row| Media::from_row_ref(&row).map_err(ServiceError::from)).transpose()
+}
+
+/// 행은 이미 지워졌으므로 파일 삭제에 실패해도 되돌리지 않고 기록만 남깁니다.
+async fn remove_files_logged(storage: &dyn Storage, media: &Media) {
+    if let Err(e) = remove_files(storage, media).await {
+        tracing::warn!("미디어 파일 삭제 실패 ({}): {e}", media.key);
+    }
+}
+
+pub async fn delete(pool: &DbPool, storage: &dyn Storage, media_id: i32, force: bool) -> Result<(), ServiceError> {
+    let client = pool.get().await?;
+
+    let Some(media) = delete_row(&client, media_id, force).await? else {
+        let row = client
+            .query_opt(
+                "SELECT (SELECT COUNT(*) FROM media_usages WHERE media_id = $1) FROM media WHERE id = $1",
+                &[&media_id]
+            ).await?
+            .ok_or(ServiceError::NotFound)?;
+        let usage_count: i64 = row.get(0);
+        return Err(ServiceError::BadRequest(format!("{}개의 글에서 사용 중인 미디어입니다", usage_count)));
+    };
+
+    remove_files_logged(storage, &media).await;
+    Ok(())
+}
+
+/// 썸네일 URL 을 라이브러리 미디어로 등록합니다. 이미 있는 미디어면 그대로 돌려주고,
+/// 외부 이미지는 원본과 변환본을 저장해 고아 정리 대상이 되도록 행을 만듭니다.
+pub async fn import_url(
+    pool: &DbPool,
+    storage: &dyn Storage,
+    image_cfg: &ImageConfig,
+    url: &str
+) -> Result<Media, ServiceError> {
+    let client = pool.get().await?;
+    let existing = client
+        .query_opt(&format!("SELECT {} FROM media WHERE url = $1", MEDIA_COLUMNS), &[&url]).await?;
+    if let Some(row) = existing {
+        return Ok(Media::from_row_ref(&row)?);
+    }
+    drop(client);
+
+    let bytes = load_source(storage, url).await?;
+    let filename = url.rsplit('/').next().unwrap_or_default().split(['?', '#']).next().unwrap_or_default();
+    upload(pool, storage, image_cfg, filename, bytes).await
+}
+
+/// 텍스트에서 미디어 키/URL 에 쓰이는 SHA-256 해시(16진수 64자)를 모두 찾습니다.
+pub fn referenced_hashes<'a>(texts: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut hashes = Vec::new();
+    for text in texts {
+        let mut run_start = None;
+        for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+            let is_hex = c.is_ascii_digit() || ('a'..='f').contains(&c);
+            match (is_hex, run_start) {
+                (true, None) => {
+                    run_start = Some(i);
+                }
+                (false, Some(start)) => {
+                    if i - start == 64 {
+                        hashes.push(text[start..i].to_string());
+                    }
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    hashes.sort_unstable();
+    hashes.dedup();
+    hashes
+}
+
+/// 글 본문과 썸네일이 참조하는 미디어로 사용 기록을 갱신합니다.
+/// 썸네일 변환본 URL 도 함께 봅니다. 외부 썸네일은 변환본으로만 라이브러리 미디어와 이어집니다.
+pub async fn sync_usages(
+    client: &impl GenericClient,
+    post_id: i32,
+    body: &str,
+    thumbnail: &str,
+    thumbnail_variants: &ImageVariants
+) -> Result<(), ServiceError> {
+    let variant_urls = thumbnail_variants.0.iter().map(|v| v.url.as_str());
+    let hashes = referenced_hashes([body, thumbnail].into_iter().chain(variant_urls));
+
+    let delete_stmt = client.prepare_cached("DELETE FROM media_usages WHERE post_id = $1").await?;
+    client.execute(&delete_stmt, &[&post_id]).await?;
+
+    if hashes.is_empty() {
+        return Ok(());
+    }
+
+    let insert_stmt = client
+        .prepare_cached(
+            "INSERT INTO media_usages (media_id, post_id)
+             SELECT id, $1 FROM media WHERE hash = ANY($2)
+             ON CONFLICT DO NOTHING"
+        ).await?;
+    client.execute(&insert_stmt, &[&post_id, &hashes]).await?;
+
+    Ok(())
+}
+
+pub async fn rescan_usages(pool: &DbPool) -> Result<(), ServiceError> {
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    let rows = tx.query("SELECT id, body, thumbnail, thumbnail_variants FROM posts", &[]).await?;
+    for row in rows {
+        sync_usages(&tx, row.get(0), row.get(1), row.get(2), &row.get(3)).await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn cleanup_orphans(
+    pool: &DbPool,
+    storage: &dyn Storage,
+    min_age_hours: u32,
+    dry_run: bool
+) -> Result<OrphanReport, ServiceError> {
+    rescan_usages(pool).await?;
+
+    let client = pool.get().await?;
+
+    let stmt = client
+        .prepare_cached(
+            &format!(
+                "SELECT {}
+                 FROM media
+                 WHERE NOT EXISTS (SELECT 1 FROM media_usages u WHERE u.media_id = media.id)
+                   AND created_at < NOW() - make_interval(hours => $1)
+                 ORDER BY created_at",
+                MEDIA_COLUMNS
+            )
+        ).await?;
+    let rows = client.query(&stmt, &[&(min_age_hours as i32)]).await?;
+
+    let orphans = rows
+        .into_iter()
+        .map(|row| Media::from_row_ref(&row).map_err(ServiceError::from))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut freed_bytes = orphans.iter().map(|m| m.size).sum();
+
+    let mut deleted = 0;
+    if !dry_run {
+        // 점검 뒤에 다시 쓰이기 시작한 미디어는 delete_row 가 건너뜁니다.
+        freed_bytes = 0;
+        for media in &orphans {
+            if let Some(media) = delete_row(&client, media.id, false).await? {
+                remove_files_logged(storage, &media).await;
+                freed_bytes += media.size;
+                deleted += 1;
+            }
+        }
+    }
+
+    Ok(OrphanReport { dry_run, orphans, deleted, freed_bytes })
+}
+
+/// `orphan_cleanup_interval_hours` 마다 고아 미디어를 점검합니다.
+pub async fn run_orphan_cleanup(pool: DbPool, storage: Arc<dyn Storage>, cfg: StorageConfig) {
+    let period = Duration::from_secs(u64::from(cfg.orphan_cleanup_interval_hours) * 3600);
+    let mut interval = actix_web::rt::time::interval(period);
+
+    loop {
+        interval.tick().await;
+
+        let dry_run = !cfg.orphan_cleanup_delete;
+        match cleanup_orphans(&pool, storage.as_ref(), cfg.orphan_min_age_hours, dry_run).await {
+            Ok(report) if report.orphans.is_empty() => {}
+            Ok(report) => {
+                let keys: Vec<&str> = report.orphans.iter().map(|m| m.key.as_str()).collect();
+                tracing::info!(
+                    "고아 미디어 {}개 ({} bytes), 삭제 {}개: {:?}",
+                    report.orphans.len(),
+                    report.freed_bytes,
+                    report.deleted,
+                    keys
+                );
+            }
+            Err(e) => tracing::error!("고아 미디어 점검 실패: {e}"),
+        }
+    }
+}
+
+/// 서명과 파라미터만 확인한 요청입니다. `etag` 는 파일을 읽지 않고도 알 수 있습니다.
+pub struct ResizeRequest {
+    pub etag: String,
+    pub content_type: &'static str,
+    key: String,
+    params: ResizeParams,
+    cache_name: String,
+}
+
+pub fn resize_request(
+    image_cfg: &ImageConfig,
+    key: &str,
+    params: ResizeParams,
+    sig: &str
+) -> Result<ResizeRequest, ServiceError> {
+    if !proxy::verify(&image_cfg.proxy_secret, key, &params, sig) {
+        return Err(ServiceError::Unauthorized);
+    }
+    if key.contains('/') {
+        return Err(ServiceError::NotFound);
+    }
+    params.validate(image_cfg.proxy_max_dimension)?;
+
+    let cache_name = proxy::cache_name(key, &params);
+    Ok(ResizeRequest {
+        etag: cache_name.split('.').next().unwrap_or_default().to_string(),
+        content_type: params.fmt.content_type(),
+        key: key.to_string(),
+        params,
+        cache_name,
+    })
+}
+
+pub async fn resized_image(
+    storage: &dyn Storage,
+    cache: &DiskCache,
+    image_cfg: &ImageConfig,
+    request: ResizeRequest
+) -> Result<Vec<u8>, ServiceError> {
+    let ResizeRequest { key, params, cache_name: name, .. } = request;
+
+    if let Some(bytes) = cache.get(&name).await {
+        return Ok(bytes);
+    }
+
+    let original = storage.get(&key).await?;
+    let image_cfg = image_cfg.clone();
+    let bytes = web::block(move || proxy::render(&original, &params, &image_cfg)).await??;
+
+    if let Err(e) = cache.put(&name, &bytes).await {
+        tracing::warn!("이미지 캐시 저장 실패 ({name}): {e}");
+    }
+
+    Ok(bytes)
+}
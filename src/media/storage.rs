@@ -0,0 +1,247 @@
+use std::path::{ Path, PathBuf };
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{ Hmac, Mac };
+use reqwest::{ Client, Method, StatusCode, Url };
+use sha2::{ Digest, Sha256 };
+
+use crate::config::{ StorageBackend, StorageConfig };
+use crate::errors::ServiceError;
+
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), ServiceError>;
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, ServiceError>;
+
+    async fn delete(&self, key: &str) -> Result<(), ServiceError>;
+
+    fn public_url(&self, key: &str) -> String;
+
+    fn key_from_url(&self, url: &str) -> Option<String> {
+        url.strip_prefix(&self.public_url(""))
+            .filter(|key| validate_key(key).is_ok())
+            .map(str::to_string)
+    }
+}
+
+pub fn init_storage(cfg: &StorageConfig) -> Result<Arc<dyn Storage>, String> {
+    match cfg.backend {
+        StorageBackend::Local => Ok(Arc::new(LocalStorage::new(&cfg.local_dir, &cfg.public_base_url))),
+        StorageBackend::S3 => Ok(Arc::new(S3Storage::new(cfg)?)),
+    }
+}
+
+fn validate_key(key: &str) -> Result<(), ServiceError> {
+    let valid =
+        !key.is_empty() &&
+        key.split('/').all(|seg| !seg.is_empty() && seg != "." && seg != "..") &&
+        key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+
+    if valid { Ok(()) } else { Err(ServiceError::BadRequest(format!("잘못된 파일 경로: {}", key))) }
+}
+
+pub struct LocalStorage {
+    root: PathBuf,
+    public_base_url: String,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl AsRef<Path>, public_base_url: &str) -> Self {
+        LocalStorage {
+            root: root.as_ref().to_path_buf(),
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, ServiceError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), ServiceError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, bytes).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, ServiceError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(ServiceError::NotFound),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), ServiceError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_base_url, key)
+    }
+}
+
+/// MinIO 등 S3 호환 스토리지. path-style URL(`{endpoint}/{bucket}/{key}`)과 SigV4 서명을 사용합니다.
+pub struct S3Storage {
+    client: Client,
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    public_base_url: String,
+}
+
+impl S3Storage {
+    pub fn new(cfg: &StorageConfig) -> Result<Self, String> {
+        let endpoint = cfg.s3_endpoint.as_deref().ok_or("STORAGE__S3_ENDPOINT 가 필요합니다")?;
+        let endpoint = Url::parse(endpoint.trim_end_matches('/')).map_err(|e| e.to_string())?;
+        let bucket = cfg.s3_bucket.clone().ok_or("STORAGE__S3_BUCKET 이 필요합니다")?;
+        let access_key = cfg.s3_access_key.clone().ok_or("STORAGE__S3_ACCESS_KEY 가 필요합니다")?;
+        let secret_key = cfg.s3_secret_key.clone().ok_or("STORAGE__S3_SECRET_KEY 가 필요합니다")?;
+
+        let public_base_url = if cfg.public_base_url.starts_with("http") {
+            cfg.public_base_url.trim_end_matches('/').to_string()
+        } else {
+            format!("{}/{}", endpoint.as_str().trim_end_matches('/'), bucket)
+        };
+
+        Ok(S3Storage {
+            client: Client::new(),
+            endpoint,
+            bucket,
+            region: cfg.s3_region.clone(),
+            access_key,
+            secret_key,
+            public_base_url,
+        })
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>
+    ) -> Result<reqwest::Response, ServiceError> {
+        validate_key(key)?;
+
+        let mut url = self.endpoint.clone();
+        url.set_path(&format!("{}/{}", self.bucket, key));
+
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method.as_str(),
+            url.path(),
+            host,
+            payload_hash,
+            amz_date,
+            payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let k_date = hmac_sha256(format!("AWS4{}", self.secret_key).as_bytes(), date.as_bytes());
+        let k_region = hmac_sha256(&k_date, self.region.as_bytes());
+        let k_service = hmac_sha256(&k_region, b"s3");
+        let k_signing = hmac_sha256(&k_service, b"aws4_request");
+        let signature = hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+            self.access_key,
+            scope,
+            signature
+        );
+
+        let mut request = self.client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("authorization", authorization);
+        if let Some(content_type) = content_type {
+            request = request.header("content-type", content_type);
+        }
+
+        request
+            .body(body)
+            .send().await
+            .map_err(|e| ServiceError::InternalServerError(format!("스토리지 요청 실패: {}", e)))
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, ServiceError> {
+    let status = response.status();
+    if status == StatusCode::NOT_FOUND {
+        return Err(ServiceError::NotFound);
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ServiceError::InternalServerError(format!("스토리지 오류 {}: {}", status, body)));
+    }
+    Ok(response)
+}
+
+#[async_trait]
+impl Storage for S3Storage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), ServiceError> {
+        let response = self.send(Method::PUT, key, bytes, Some(content_type)).await?;
+        check_status(response).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, ServiceError> {
+        let response = check_status(self.send(Method::GET, key, Vec::new(), None).await?).await?;
+        let bytes = response
+            .bytes().await
+            .map_err(|e| ServiceError::InternalServerError(e.to_string()))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), ServiceError> {
+        match check_status(self.send(Method::DELETE, key, Vec::new(), None).await?).await {
+            Ok(_) | Err(ServiceError::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_base_url, key)
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::blog::model::PostSummary;
+use crate::series::model::Series;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSeries {
+    pub title: String,
+    pub slug: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSeries {
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+}
+
+/// 시리즈에 넣을 글 ID 를 순서대로 보냅니다. 기존 목록은 통째로 바뀝니다.
+#[derive(Debug, Deserialize)]
+pub struct SeriesPostsRequest {
+    pub post_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeriesSummary {
+    #[serde(flatten)]
+    pub series: Series,
+    pub post_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeriesDetail {
+    #[serde(flatten)]
+    pub series: Series,
+    /// 편 순서대로
+    pub posts: Vec<PostSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeriesPart {
+    pub post_id: i32,
+    pub title: String,
+    /// 1 부터 시작하는 편 번호
+    pub part: i32,
+}
+
+/// 글 상세에 붙이는 시리즈 정보. "M편 중 N편" 과 앞뒤 편을 담습니다.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeriesNav {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub part: i32,
+    pub total: i32,
+    pub previous: Option<SeriesPart>,
+    pub next: Option<SeriesPart>,
+    pub parts: Vec<SeriesPart>,
+}
@@ -0,0 +1,76 @@
+use actix_web::{ delete, get, post, put, web, HttpResponse, Responder, ResponseError };
+
+use crate::db::DbPool;
+use crate::series::dto::{ CreateSeries, SeriesPostsRequest, UpdateSeries };
+use crate::series::service;
+use crate::user::handlers::Admin;
+
+#[get("/series")]
+pub async fn list_series(pool: web::Data<DbPool>) -> impl Responder {
+    match service::list(&pool).await {
+        Ok(series) => { HttpResponse::Ok().json(series) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/series/{slug}")]
+pub async fn get_series(pool: web::Data<DbPool>, path: web::Path<String>) -> impl Responder {
+    let slug = path.into_inner();
+
+    match service::get_by_slug(&pool, &slug).await {
+        Ok(series) => { HttpResponse::Ok().json(series) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/admin/series")]
+pub async fn create_series(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Json(dto): web::Json<CreateSeries>
+) -> impl Responder {
+    match service::create(&pool, dto).await {
+        Ok(series) => { HttpResponse::Created().json(series) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[put("/admin/series/{id}")]
+pub async fn update_series(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    path: web::Path<i32>,
+    web::Json(dto): web::Json<UpdateSeries>
+) -> impl Responder {
+    let id = path.into_inner();
+
+    match service::update(&pool, id, dto).await {
+        Ok(series) => { HttpResponse::Ok().json(series) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[delete("/admin/series/{id}")]
+pub async fn delete_series(_: Admin, pool: web::Data<DbPool>, path: web::Path<i32>) -> impl Responder {
+    let id = path.into_inner();
+
+    match service::delete(&pool, id).await {
+        Ok(_) => { HttpResponse::NoContent().finish() }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[put("/admin/series/{id}/posts")]
+pub async fn set_series_posts(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    path: web::Path<i32>,
+    web::Json(dto): web::Json<SeriesPostsRequest>
+) -> impl Responder {
+    let id = path.into_inner();
+
+    match service::set_posts(&pool, id, &dto.post_ids).await {
+        Ok(series) => { HttpResponse::Ok().json(series) }
+        Err(e) => { e.error_response() }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod model;
+pub mod dto;
+pub mod service;
+pub mod handlers;
+pub mod routes;
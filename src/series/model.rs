@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+use serde::{ Deserialize, Serialize };
+use tokio_pg_mapper_derive::PostgresMapper;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PostgresMapper)]
+#[pg_mapper(table = "series")]
+pub struct Series {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: String,
+    pub created_at: NaiveDateTime,
+}
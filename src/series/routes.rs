@@ -0,0 +1,11 @@
+use actix_web::web;
+use crate::series::handlers;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(handlers::list_series)
+        .service(handlers::get_series)
+        .service(handlers::create_series)
+        .service(handlers::update_series)
+        .service(handlers::delete_series)
+        .service(handlers::set_series_posts);
+}
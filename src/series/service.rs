@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+
+use deadpool_postgres::GenericClient;
+use tokio_pg_mapper::FromTokioPostgresRow;
+
+use crate::blog::model::PostSummary;
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::series::dto::{ CreateSeries, SeriesDetail, SeriesNav, SeriesPart, SeriesSummary, UpdateSeries };
+use crate::series::model::Series;
+use crate::slug::{ slug_conflict, validate_slug };
+
+const SERIES_COLUMNS: &str = "id, title, slug, description, created_at";
+
+const MAX_TITLE_CHARS: usize = 255;
+
+fn validate_title(title: &str) -> Result<String, ServiceError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(ServiceError::BadRequest("시리즈 제목을 입력해주세요".into()));
+    }
+    if title.chars().count() > MAX_TITLE_CHARS {
+        return Err(ServiceError::BadRequest(format!("시리즈 제목은 {}자를 넘을 수 없습니다", MAX_TITLE_CHARS)));
+    }
+    Ok(title.to_string())
+}
+
+pub async fn list(pool: &DbPool) -> Result<Vec<SeriesSummary>, ServiceError> {
+    let client = pool.get().await?;
+
+    let rows = client
+        .query(
+            "SELECT s.id, s.title, s.slug, s.description, s.created_at, COUNT(p.id) AS post_count
+             FROM series s
+             LEFT JOIN series_posts sp ON sp.series_id = s.id
+             LEFT JOIN posts p ON p.id = sp.post_id AND p.status = 'published'
+             GROUP BY s.id
+             ORDER BY s.created_at DESC, s.id DESC",
+            &[]
+        ).await?;
+
+    rows.into_iter()
+        .map(|row| Ok(SeriesSummary { series: Series::from_row_ref(&row)?, post_count: row.get("post_count") }))
+        .collect()
+}
+
+async fn detail(client: &impl GenericClient, series: Series) -> Result<SeriesDetail, ServiceError> {
+    let rows = client
+        .query(
+            "SELECT p.id, p.title, p.description, p.tags, p.thumbnail, p.thumbnail_blur, p.thumbnail_variants,
+                    p.view_count, p.reactions, p.created_at
+             FROM series_posts sp
+             JOIN posts p ON p.id = sp.post_id
+             WHERE sp.series_id = $1 AND p.status = 'published'
+             ORDER BY sp.position",
+            &[&series.id]
+        ).await?;
+
+    let posts = rows
+        .into_iter()
+        .map(|row| PostSummary::from_row_ref(&row).map_err(ServiceError::from))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SeriesDetail { series, posts })
+}
+
+pub async fn get_by_slug(pool: &DbPool, slug: &str) -> Result<SeriesDetail, ServiceError> {
+    let client = pool.get().await?;
+
+    let row = client
+        .query_opt(&format!("SELECT {} FROM series WHERE slug = $1", SERIES_COLUMNS), &[&slug]).await?
+        .ok_or(ServiceError::NotFound)?;
+
+    detail(&client, Series::from_row_ref(&row)?).await
+}
+
+pub async fn create(pool: &DbPool, dto: CreateSeries) -> Result<Series, ServiceError> {
+    let title = validate_title(&dto.title)?;
+    let slug = validate_slug(&dto.slug)?;
+
+    let client = pool.get().await?;
+
+    let row = client
+        .query_one(
+            &format!(
+                "INSERT INTO series (title, slug, description) VALUES ($1, $2, $3) RETURNING {}",
+                SERIES_COLUMNS
+            ),
+            &[&title, &slug, &dto.description.trim()]
+        ).await
+        .map_err(slug_conflict)?;
+
+    Ok(Series::from_row_ref(&row)?)
+}
+
+pub async fn update(pool: &DbPool, series_id: i32, dto: UpdateSeries) -> Result<Series, ServiceError> {
+    let title = dto.title.as_deref().map(validate_title).transpose()?;
+    let slug = dto.slug.as_deref().map(validate_slug).transpose()?;
+    let description = dto.description.as_deref().map(str::trim);
+
+    let client = pool.get().await?;
+
+    let row = client
+        .query_opt(
+            &format!(
+                "UPDATE series SET
+                     title = COALESCE($1, title),
+                     slug = COALESCE($2, slug),
+                     description = COALESCE($3, description)
+                 WHERE id = $4
+                 RETURNING {}",
+                SERIES_COLUMNS
+            ),
+            &[&title, &slug, &description, &series_id]
+        ).await
+        .map_err(slug_conflict)?
+        .ok_or(ServiceError::NotFound)?;
+
+    Ok(Series::from_row_ref(&row)?)
+}
+
+pub async fn delete(pool: &DbPool, series_id: i32) -> Result<(), ServiceError> {
+    let client = pool.get().await?;
+
+    let deleted = client.execute("DELETE FROM series WHERE id = $1", &[&series_id]).await?;
+    if deleted == 0 {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(())
+}
+
+/// 글은 한 시리즈에만 속할 수 있습니다. 다른 시리즈의 글이 섞여 있으면 거절합니다.
+pub async fn set_posts(pool: &DbPool, series_id: i32, post_ids: &[i32]) -> Result<SeriesDetail, ServiceError> {
+    let unique: HashSet<i32> = post_ids.iter().copied().collect();
+    if unique.len() != post_ids.len() {
+        return Err(ServiceError::BadRequest("같은 글을 두 번 넣을 수 없습니다".into()));
+    }
+
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    let row = tx
+        .query_opt(&format!("SELECT {} FROM series WHERE id = $1 FOR UPDATE", SERIES_COLUMNS), &[&series_id]).await?
+        .ok_or(ServiceError::NotFound)?;
+    let series = Series::from_row_ref(&row)?;
+
+    let found: i64 = tx
+        .query_one("SELECT COUNT(*) FROM posts WHERE id = ANY($1)", &[&post_ids]).await?
+        .get(0);
+    if found != post_ids.len() as i64 {
+        return Err(ServiceError::BadRequest("존재하지 않는 글이 있습니다".into()));
+    }
+
+    let taken: Vec<i32> = tx
+        .query(
+            "SELECT post_id FROM series_posts WHERE post_id = ANY($1) AND series_id <> $2 ORDER BY post_id",
+            &[&post_ids, &series_id]
+        ).await?
+        .into_iter()
+        .map(|row| row.get(0))
+        .collect();
+    if !taken.is_empty() {
+        let ids = taken.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+        return Err(ServiceError::BadRequest(format!("이미 다른 시리즈에 속한 글입니다: {}", ids)));
+    }
+
+    tx.execute("DELETE FROM series_posts WHERE series_id = $1", &[&series_id]).await?;
+    tx.execute(
+        "INSERT INTO series_posts (series_id, post_id, position)
+         SELECT $1, t.post_id, t.position::int FROM unnest($2::int[]) WITH ORDINALITY AS t(post_id, position)",
+        &[&series_id, &post_ids]
+    ).await?;
+
+    let detail = detail(&tx, series).await?;
+    tx.commit().await?;
+
+    Ok(detail)
+}
+
+/// 글이 시리즈에 속해 있으면 몇 번째 편인지와 앞뒤 편을 돌려줍니다.
+pub async fn nav_for_post(client: &impl GenericClient, post_id: i32) -> Result<Option<SeriesNav>, ServiceError> {
+    let Some(row) = client
+        .query_opt(
+            "SELECT s.id, s.title, s.slug FROM series_posts sp JOIN series s ON s.id = sp.series_id
+             WHERE sp.post_id = $1",
+            &[&post_id]
+        ).await? else {
+        return Ok(None);
+    };
+    let series_id: i32 = row.get(0);
+
+    let parts: Vec<SeriesPart> = client
+        .query(
+            "SELECT p.id, p.title, sp.position FROM series_posts sp JOIN posts p ON p.id = sp.post_id
+             WHERE sp.series_id = $1 AND (p.status = 'published' OR p.id = $2)
+             ORDER BY sp.position",
+            &[&series_id, &post_id]
+        ).await?
+        .into_iter()
+        .map(|row| SeriesPart { post_id: row.get(0), title: row.get(1), part: row.get(2) })
+        .collect();
+
+    let index = parts.iter().position(|p| p.post_id == post_id).unwrap_or_default();
+
+    Ok(
+        Some(SeriesNav {
+            id: series_id,
+            title: row.get(1),
+            slug: row.get(2),
+            part: parts[index].part,
+            total: parts.len() as i32,
+            previous: index.checked_sub(1).map(|i| parts[i].clone()),
+            next: parts.get(index + 1).cloned(),
+            parts,
+        })
+    )
+}
@@ -0,0 +1,25 @@
+use crate::errors::ServiceError;
+
+const MAX_SLUG_CHARS: usize = 100;
+
+/// 슬러그는 URL 에 그대로 쓰므로 소문자, 숫자, 하이픈만 허용합니다.
+pub fn validate_slug(slug: &str) -> Result<String, ServiceError> {
+    let slug = slug.trim().to_lowercase();
+    let valid = !slug.is_empty() &&
+        slug.len() <= MAX_SLUG_CHARS &&
+        slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') &&
+        !slug.starts_with('-') &&
+        !slug.ends_with('-');
+    if !valid {
+        return Err(ServiceError::BadRequest("슬러그는 영문 소문자, 숫자, 하이픈만 쓸 수 있습니다".into()));
+    }
+    Ok(slug)
+}
+
+pub fn slug_conflict(e: tokio_postgres::Error) -> ServiceError {
+    if e.code() == Some(&tokio_postgres::error::SqlState::UNIQUE_VIOLATION) {
+        ServiceError::BadRequest("이미 사용 중인 슬러그입니다".into())
+    } else {
+        ServiceError::from(e)
+    }
+}
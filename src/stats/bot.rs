@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use actix_web::{ http::header, HttpRequest };
+
+use crate::config::StatsConfig;
+use crate::visitor::{ client_ip, user_agent, RateLimiter };
+
+/// 검색엔진 크롤러, 링크 미리보기 봇, HTTP 라이브러리의 User-Agent 조각 (소문자).
+/// 구체적인 이름을 앞에 두어 통계에 어떤 봇인지 남도록 합니다.
+pub const BOT_AGENTS: &[&str] = &[
+    // 검색엔진
+    "googlebot",
+    "google-inspectiontool",
+    "storebot-google",
+    "adsbot-google",
+    "mediapartners-google",
+    "bingbot",
+    "bingpreview",
+    "msnbot",
+    "yeti",
+    "daumoa",
+    "yandex",
+    "baiduspider",
+    "duckduckbot",
+    "slurp",
+    "applebot",
+    "seznambot",
+    "sogou",
+    "exabot",
+    "petalbot",
+    // SEO / 수집기
+    "ahrefsbot",
+    "semrushbot",
+    "mj12bot",
+    "dotbot",
+    "rogerbot",
+    "screaming frog",
+    "bytespider",
+    "gptbot",
+    "chatgpt-user",
+    "oai-searchbot",
+    "claudebot",
+    "anthropic-ai",
+    "perplexitybot",
+    "ccbot",
+    "amazonbot",
+    "dataforseobot",
+    // 링크 미리보기
+    "facebookexternalhit",
+    "facebot",
+    "twitterbot",
+    "slackbot",
+    "discordbot",
+    "telegrambot",
+    "whatsapp",
+    "linkedinbot",
+    "kakaotalk-scrap",
+    "skypeuripreview",
+    "embedly",
+    "pinterest",
+    "redditbot",
+    "mastodon",
+    // 헤드리스 브라우저와 HTTP 클라이언트
+    "headlesschrome",
+    "phantomjs",
+    "lighthouse",
+    "python-requests",
+    "python-urllib",
+    "aiohttp",
+    "httpx",
+    "curl/",
+    "wget/",
+    "go-http-client",
+    "okhttp",
+    "java/",
+    "apache-httpclient",
+    "node-fetch",
+    "axios/",
+    "libwww-perl",
+    "scrapy",
+    // 일반 표기
+    "bot",
+    "crawler",
+    "spider",
+    "scraper",
+];
+
+pub struct BotDetector {
+    extra_agents: Vec<String>,
+    rate: RateLimiter,
+}
+
+impl BotDetector {
+    pub fn new(cfg: &StatsConfig) -> Self {
+        BotDetector {
+            extra_agents: cfg.bot_agents(),
+            rate: RateLimiter::new(cfg.bot_views_per_minute, Duration::from_secs(60)),
+        }
+    }
+
+    /// 봇으로 판단되면 그 사유를 돌려줍니다. 조회 요청마다 한 번씩 불러야 요청 빈도가 맞게 셉니다.
+    pub fn classify(&self, req: &HttpRequest) -> Option<String> {
+        if let Some(reason) = self.classify_agent(req) {
+            return Some(reason);
+        }
+
+        // 브라우저는 fetch 요청에도 Accept-Language 를 붙입니다.
+        if !req.headers().contains_key(header::ACCEPT_LANGUAGE) {
+            return Some("no-accept-language".into());
+        }
+        if !self.rate.allow(&client_ip(req)) {
+            return Some("rate".into());
+        }
+        None
+    }
+
+    /// User-Agent 만 봅니다. 요청 빈도를 세지 않으므로 조회 외의 요청에 씁니다.
+    pub fn classify_agent(&self, req: &HttpRequest) -> Option<String> {
+        let agent = user_agent(req).trim().to_lowercase();
+        if agent.is_empty() {
+            return Some("no-user-agent".into());
+        }
+        if let Some(pattern) = self.extra_agents
+            .iter()
+            .map(String::as_str)
+            .chain(BOT_AGENTS.iter().copied())
+            .find(|pattern| agent.contains(pattern))
+        {
+            return Some(format!("ua:{}", pattern));
+        }
+        None
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::NaiveDate;
+use serde::{ Deserialize, Serialize };
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BotReasonHits {
+    pub reason: String,
+    pub hits: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DailyHits {
+    pub day: NaiveDate,
+    pub hits: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BotTrafficReport {
+    pub days: u32,
+    pub total_hits: i64,
+    pub by_reason: Vec<BotReasonHits>,
+    pub by_day: Vec<DailyHits>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatsPoint {
+    /// 구간의 시작일. 주 단위는 월요일, 월 단위는 1일입니다.
+    pub period: NaiveDate,
+    pub views: i64,
+    pub visitors: i64,
+    pub reactions: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimeSeries {
+    pub post_id: Option<i32>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub granularity: Granularity,
+    pub total_views: i64,
+    pub total_visitors: i64,
+    pub total_reactions: i64,
+    pub points: Vec<StatsPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SourceHits {
+    pub source: String,
+    pub hits: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReferrerHits {
+    pub host: String,
+    pub source: String,
+    pub hits: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReferrerReport {
+    pub post_id: Option<i32>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub sources: Vec<SourceHits>,
+    pub referrers: Vec<ReferrerHits>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CampaignHits {
+    pub utm_source: String,
+    pub utm_medium: String,
+    pub utm_campaign: String,
+    pub hits: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CampaignReport {
+    pub post_id: Option<i32>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub campaigns: Vec<CampaignHits>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostEngagement {
+    pub post_id: i32,
+    pub title: String,
+    pub reads: i64,
+    pub avg_read_secs: f64,
+    /// 끝까지 스크롤한 비율 (0~1)
+    pub completion_rate: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EngagementReport {
+    pub post_id: Option<i32>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub reads: i64,
+    pub avg_read_secs: f64,
+    pub completion_rate: f64,
+    /// 읽은 수가 많은 순
+    pub posts: Vec<PostEngagement>,
+}
@@ -0,0 +1,273 @@
+use actix_web::web::Bytes;
+use chrono::NaiveDate;
+use deadpool_postgres::Client;
+use futures_util::{ stream, Stream, StreamExt, TryStreamExt };
+use serde::{ Deserialize, Serialize };
+use tokio_pg_mapper::FromTokioPostgresRow;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{ Row, RowStream };
+
+use crate::blog::model::PostSummary;
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::tag::service as tag_service;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+/// 내보내기 한 줄. CSV 열 순서는 `HEADER` 와 `csv_fields` 가 맞춰야 합니다.
+pub trait ExportRow: Serialize {
+    const HEADER: &'static [&'static str];
+
+    fn csv_fields(&self) -> Vec<String>;
+}
+
+/// 글 정보와 기간 안의 합계
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostExport {
+    #[serde(flatten)]
+    pub post: PostSummary,
+    pub views: i64,
+    pub visitors: i64,
+    /// 기간 안에 새로 남긴 반응 수
+    pub reactions_added: i64,
+    pub reads: i64,
+    pub read_secs: i64,
+}
+
+impl ExportRow for PostExport {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "title",
+        "description",
+        "tags",
+        "thumbnail",
+        "view_count",
+        "reactions",
+        "created_at",
+        "views",
+        "visitors",
+        "reactions_added",
+        "reads",
+        "read_secs",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        let post = &self.post;
+        vec![
+            post.id.to_string(),
+            post.title.clone(),
+            post.description.clone(),
+            post.tags.join(","),
+            post.thumbnail.clone(),
+            post.view_count.to_string(),
+            serde_json::to_string(&post.reactions).unwrap_or_default(),
+            post.created_at.to_string(),
+            self.views.to_string(),
+            self.visitors.to_string(),
+            self.reactions_added.to_string(),
+            self.reads.to_string(),
+            self.read_secs.to_string(),
+        ]
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DailyExport {
+    pub day: NaiveDate,
+    pub post_id: i32,
+    pub title: String,
+    pub views: i32,
+    pub visitors: i32,
+    pub reactions: i32,
+    pub reads: i32,
+    pub read_secs: i64,
+}
+
+impl ExportRow for DailyExport {
+    const HEADER: &'static [&'static str] = &[
+        "day",
+        "post_id",
+        "title",
+        "views",
+        "visitors",
+        "reactions",
+        "reads",
+        "read_secs",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.day.to_string(),
+            self.post_id.to_string(),
+            self.title.clone(),
+            self.views.to_string(),
+            self.visitors.to_string(),
+            self.reactions.to_string(),
+            self.reads.to_string(),
+            self.read_secs.to_string(),
+        ]
+    }
+}
+
+fn csv_line<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+fn encode<T: ExportRow>(format: ExportFormat, row: &T) -> Result<Bytes, ServiceError> {
+    match format {
+        ExportFormat::Csv => Ok(Bytes::from(csv_line(&row.csv_fields()))),
+        ExportFormat::Ndjson => {
+            let mut line = serde_json::to_vec(row).map_err(|e| ServiceError::InternalServerError(e.to_string()))?;
+            line.push(b'\n');
+            Ok(Bytes::from(line))
+        }
+    }
+}
+
+/// 행을 하나씩 읽어 바로 내보냅니다. 스트림이 끝날 때까지 커넥션을 붙잡아 둡니다.
+fn rows_with_client(client: Client, rows: RowStream) -> impl Stream<Item = Result<Row, ServiceError>> {
+    stream::unfold((client, Box::pin(rows)), |(client, mut rows)| async move {
+        let row = rows.next().await?;
+        Some((row.map_err(ServiceError::from), (client, rows)))
+    })
+}
+
+fn encode_rows<T: ExportRow + 'static>(
+    format: ExportFormat,
+    rows: impl Stream<Item = Result<T, ServiceError>> + 'static
+) -> impl Stream<Item = Result<Bytes, ServiceError>> {
+    let header = match format {
+        ExportFormat::Csv => Some(Ok(Bytes::from(csv_line(T::HEADER)))),
+        ExportFormat::Ndjson => None,
+    };
+    stream::iter(header).chain(rows.and_then(move |row| async move { encode(format, &row) }))
+}
+
+async fn query_stream(
+    pool: &DbPool,
+    sql: &str,
+    params: &[&(dyn ToSql + Sync)]
+) -> Result<impl Stream<Item = Result<Row, ServiceError>> + use<>, ServiceError> {
+    let client = pool.get().await?;
+    let stmt = client.prepare_cached(sql).await?;
+    let rows = client.query_raw(&stmt, params.iter().copied()).await?;
+    Ok(rows_with_client(client, rows))
+}
+
+pub async fn posts(
+    pool: &DbPool,
+    format: ExportFormat,
+    from: NaiveDate,
+    to: NaiveDate,
+    tag: Option<&str>
+) -> Result<impl Stream<Item = Result<Bytes, ServiceError>> + use<>, ServiceError> {
+    let tag = tag.map(tag_service::normalize).transpose()?;
+    let rows = query_stream(
+        pool,
+        "SELECT p.id, p.title, p.description, p.tags, p.thumbnail, p.thumbnail_blur, p.thumbnail_variants,
+                p.view_count, p.reactions, p.created_at,
+                COALESCE(s.views, 0) AS views,
+                COALESCE(s.visitors, 0) AS visitors,
+                COALESCE(s.reactions, 0) AS reactions_added,
+                COALESCE(e.reads, 0) AS reads,
+                COALESCE(e.read_secs, 0) AS read_secs
+         FROM posts p
+         LEFT JOIN (
+             SELECT post_id, SUM(views)::bigint AS views, SUM(visitors)::bigint AS visitors, SUM(reactions)::bigint AS reactions
+             FROM post_daily_stats
+             WHERE day BETWEEN $1 AND $2
+             GROUP BY post_id
+         ) s ON s.post_id = p.id
+         LEFT JOIN (
+             SELECT post_id, SUM(reads)::bigint AS reads, SUM(read_secs)::bigint AS read_secs
+             FROM post_engagement
+             WHERE day BETWEEN $1 AND $2
+             GROUP BY post_id
+         ) e ON e.post_id = p.id
+         WHERE $3::text IS NULL OR $3 = ANY(p.tags)
+         ORDER BY p.id",
+        &[&from, &to, &tag]
+    ).await?;
+
+    let rows = rows.and_then(|row| async move {
+        Ok(PostExport {
+            post: PostSummary::from_row_ref(&row)?,
+            views: row.try_get("views")?,
+            visitors: row.try_get("visitors")?,
+            reactions_added: row.try_get("reactions_added")?,
+            reads: row.try_get("reads")?,
+            read_secs: row.try_get("read_secs")?,
+        })
+    });
+    Ok(encode_rows(format, rows))
+}
+
+pub async fn daily(
+    pool: &DbPool,
+    format: ExportFormat,
+    from: NaiveDate,
+    to: NaiveDate,
+    tag: Option<&str>
+) -> Result<impl Stream<Item = Result<Bytes, ServiceError>> + use<>, ServiceError> {
+    let tag = tag.map(tag_service::normalize).transpose()?;
+    let rows = query_stream(
+        pool,
+        "SELECT d.day, p.id, p.title, d.views, d.visitors, d.reactions,
+                COALESCE(e.reads, 0), COALESCE(e.read_secs, 0)
+         FROM post_daily_stats d
+         JOIN posts p ON p.id = d.post_id
+         LEFT JOIN post_engagement e ON e.day = d.day AND e.post_id = d.post_id
+         WHERE d.day BETWEEN $1 AND $2 AND ($3::text IS NULL OR $3 = ANY(p.tags))
+         ORDER BY d.day, p.id",
+        &[&from, &to, &tag]
+    ).await?;
+
+    let rows = rows.and_then(|row| async move {
+        Ok(DailyExport {
+            day: row.try_get(0)?,
+            post_id: row.try_get(1)?,
+            title: row.try_get(2)?,
+            views: row.try_get(3)?,
+            visitors: row.try_get(4)?,
+            reactions: row.try_get(5)?,
+            reads: row.try_get(6)?,
+            read_secs: row.try_get(7)?,
+        })
+    });
+    Ok(encode_rows(format, rows))
+}
@@ -0,0 +1,233 @@
+use actix_web::{ get, web, HttpResponse, Responder, ResponseError };
+use actix_web::http::header::{ ContentDisposition, DispositionParam, DispositionType };
+use chrono::{ Days, NaiveDate, Utc };
+use serde::Deserialize;
+
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::stats::dto::Granularity;
+use crate::stats::export::{ self, ExportFormat };
+use crate::stats::service;
+use crate::user::handlers::Admin;
+
+const MAX_DAYS: u32 = 365;
+/// 기간을 직접 고르는 조회와 내보내기는 3년까지 받습니다.
+const MAX_EXPORT_DAYS: i64 = 365 * 3;
+
+#[derive(Debug, Deserialize)]
+struct PeriodQuery {
+    days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RangeQuery {
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    #[serde(default)]
+    granularity: Granularity,
+}
+
+impl RangeQuery {
+    fn range(&self) -> Result<(NaiveDate, NaiveDate), ServiceError> {
+        date_range(self.from, self.to)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportQuery {
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    post_id: Option<i32>,
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    #[serde(default)]
+    format: ExportFormat,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    tag: Option<String>,
+}
+
+fn attachment(name: &str, format: ExportFormat, from: NaiveDate, to: NaiveDate) -> ContentDisposition {
+    ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![DispositionParam::Filename(format!("{name}-{from}-{to}.{}", format.extension()))],
+    }
+}
+
+/// 기본은 오늘까지 30일입니다.
+fn date_range(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<(NaiveDate, NaiveDate), ServiceError> {
+    let to = to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = from.unwrap_or(to - Days::new(29));
+    if from > to {
+        return Err(ServiceError::BadRequest("시작일이 종료일보다 늦습니다".into()));
+    }
+    if (to - from).num_days() > MAX_EXPORT_DAYS {
+        return Err(ServiceError::BadRequest(format!("조회 기간은 {MAX_EXPORT_DAYS}일을 넘을 수 없습니다")));
+    }
+    Ok((from, to))
+}
+
+#[get("/admin/stats/bots")]
+pub async fn bot_traffic(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<PeriodQuery>
+) -> impl Responder {
+    let days = query.days.unwrap_or(7).clamp(1, MAX_DAYS);
+
+    match service::bot_traffic(&pool, days).await {
+        Ok(report) => { HttpResponse::Ok().json(report) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/admin/stats/site")]
+pub async fn site_stats(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<RangeQuery>
+) -> impl Responder {
+    let (from, to) = match query.range() {
+        Ok(range) => range,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    match service::time_series(&pool, None, from, to, query.granularity).await {
+        Ok(series) => { HttpResponse::Ok().json(series) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/admin/stats/posts/{id}")]
+pub async fn post_stats(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    path: web::Path<i32>,
+    web::Query(query): web::Query<RangeQuery>
+) -> impl Responder {
+    let post_id = path.into_inner();
+    let (from, to) = match query.range() {
+        Ok(range) => range,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    match service::time_series(&pool, Some(post_id), from, to, query.granularity).await {
+        Ok(series) => { HttpResponse::Ok().json(series) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/admin/stats/referrers")]
+pub async fn referrer_report(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<ReportQuery>
+) -> impl Responder {
+    let (from, to) = match date_range(query.from, query.to) {
+        Ok(range) => range,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+    let limit = query.limit.unwrap_or(20).clamp(1, 100) as i64;
+
+    match service::referrer_report(&pool, query.post_id, from, to, limit).await {
+        Ok(report) => { HttpResponse::Ok().json(report) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/admin/stats/campaigns")]
+pub async fn campaign_report(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<ReportQuery>
+) -> impl Responder {
+    let (from, to) = match date_range(query.from, query.to) {
+        Ok(range) => range,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+    let limit = query.limit.unwrap_or(20).clamp(1, 100) as i64;
+
+    match service::campaign_report(&pool, query.post_id, from, to, limit).await {
+        Ok(report) => { HttpResponse::Ok().json(report) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/admin/stats/engagement")]
+pub async fn engagement_report(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<ReportQuery>
+) -> impl Responder {
+    let (from, to) = match date_range(query.from, query.to) {
+        Ok(range) => range,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+    let limit = query.limit.unwrap_or(20).clamp(1, 100) as i64;
+
+    match service::engagement_report(&pool, query.post_id, from, to, limit).await {
+        Ok(report) => { HttpResponse::Ok().json(report) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/admin/export/posts")]
+pub async fn export_posts(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<ExportQuery>
+) -> impl Responder {
+    let (from, to) = match date_range(query.from, query.to) {
+        Ok(range) => range,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    match export::posts(&pool, query.format, from, to, query.tag.as_deref()).await {
+        Ok(body) => {
+            HttpResponse::Ok()
+                .content_type(query.format.content_type())
+                .insert_header(attachment("posts", query.format, from, to))
+                .streaming(body)
+        }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/admin/export/daily")]
+pub async fn export_daily(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<ExportQuery>
+) -> impl Responder {
+    let (from, to) = match date_range(query.from, query.to) {
+        Ok(range) => range,
+        Err(e) => {
+            return e.error_response();
+        }
+    };
+
+    match export::daily(&pool, query.format, from, to, query.tag.as_deref()).await {
+        Ok(body) => {
+            HttpResponse::Ok()
+                .content_type(query.format.content_type())
+                .insert_header(attachment("daily", query.format, from, to))
+                .streaming(body)
+        }
+        Err(e) => { e.error_response() }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod bot;
+pub mod referrer;
+pub mod views;
+pub mod export;
+pub mod dto;
+pub mod service;
+pub mod handlers;
+pub mod routes;
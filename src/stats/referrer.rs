@@ -0,0 +1,184 @@
+use reqwest::Url;
+use serde::{ Deserialize, Serialize };
+
+const MAX_FIELD_CHARS: usize = 100;
+
+/// 검색엔진 도메인. `search.naver.com` 처럼 검색 결과 페이지만 골라야 하는 곳은 하위 도메인까지 적습니다.
+const SEARCH_HOSTS: &[&str] = &[
+    "google.com",
+    "google.co.kr",
+    "bing.com",
+    "search.naver.com",
+    "m.search.naver.com",
+    "search.daum.net",
+    "m.search.daum.net",
+    "duckduckgo.com",
+    "search.yahoo.com",
+    "yandex.ru",
+    "yandex.com",
+    "baidu.com",
+    "ecosia.org",
+    "search.brave.com",
+    "startpage.com",
+    "kagi.com",
+    "perplexity.ai",
+];
+
+const SOCIAL_HOSTS: &[&str] = &[
+    "facebook.com",
+    "fb.me",
+    "instagram.com",
+    "t.co",
+    "twitter.com",
+    "x.com",
+    "threads.net",
+    "bsky.app",
+    "linkedin.com",
+    "lnkd.in",
+    "reddit.com",
+    "news.ycombinator.com",
+    "youtube.com",
+    "pinterest.com",
+    "tiktok.com",
+    "mastodon.social",
+    "story.kakao.com",
+    "band.us",
+    "discord.com",
+    "slack.com",
+    "t.me",
+    "blog.naver.com",
+    "cafe.naver.com",
+    "velog.io",
+];
+
+/// `utm_source` 만 있고 Referer 가 없을 때 쓰는 이름 조각
+const SEARCH_NAMES: &[&str] = &["google", "bing", "naver", "daum", "duckduckgo", "yahoo", "yandex", "baidu"];
+const SOCIAL_NAMES: &[&str] = &[
+    "facebook",
+    "instagram",
+    "twitter",
+    "threads",
+    "linkedin",
+    "reddit",
+    "hackernews",
+    "youtube",
+    "kakao",
+    "discord",
+    "slack",
+    "telegram",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    Direct,
+    Search,
+    Social,
+    /// 다른 사이트의 링크
+    Referral,
+    /// 블로그 안에서 이동한 경우
+    Internal,
+}
+
+impl Source {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Source::Direct => "direct",
+            Source::Search => "search",
+            Source::Social => "social",
+            Source::Referral => "referral",
+            Source::Internal => "internal",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Campaign {
+    pub source: String,
+    pub medium: String,
+    pub campaign: String,
+}
+
+/// 정규화한 유입 경로
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ViewOrigin {
+    pub source: Source,
+    /// `www.` 를 뗀 Referer 호스트. 직접 방문이면 비어 있습니다.
+    pub referrer_host: String,
+    pub campaign: Option<Campaign>,
+}
+
+impl Default for ViewOrigin {
+    fn default() -> Self {
+        ViewOrigin { source: Source::Direct, referrer_host: String::new(), campaign: None }
+    }
+}
+
+fn matches_host(host: &str, domains: &[&str]) -> bool {
+    domains.iter().any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+}
+
+fn clean(value: Option<&str>, lowercase: bool) -> String {
+    let value: String = value.unwrap_or_default().trim().chars().take(MAX_FIELD_CHARS).collect();
+    if lowercase { value.to_lowercase() } else { value }
+}
+
+pub fn referrer_host(referrer: &str) -> Option<String> {
+    let url = Url::parse(referrer.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_lowercase();
+    Some(host.strip_prefix("www.").unwrap_or(&host).to_string())
+}
+
+pub fn classify_host(host: &str, site_hosts: &[String]) -> Source {
+    if site_hosts.iter().any(|site| host == site || host.ends_with(&format!(".{}", site))) {
+        Source::Internal
+    } else if matches_host(host, SEARCH_HOSTS) || host.starts_with("google.") {
+        Source::Search
+    } else if matches_host(host, SOCIAL_HOSTS) {
+        Source::Social
+    } else {
+        Source::Referral
+    }
+}
+
+fn classify_name(name: &str) -> Source {
+    if SEARCH_NAMES.iter().any(|n| name.contains(n)) {
+        Source::Search
+    } else if SOCIAL_NAMES.iter().any(|n| name.contains(n)) {
+        Source::Social
+    } else {
+        Source::Referral
+    }
+}
+
+impl ViewOrigin {
+    /// 프런트엔드가 보낸 `document.referrer` 와 `utm_*` 값을 정규화합니다.
+    /// `site_hosts` 는 블로그 자신의 호스트 목록으로, 여기서 온 조회는 내부 이동으로 봅니다.
+    pub fn parse(
+        referrer: Option<&str>,
+        utm_source: Option<&str>,
+        utm_medium: Option<&str>,
+        utm_campaign: Option<&str>,
+        site_hosts: &[String]
+    ) -> ViewOrigin {
+        let host = referrer.and_then(referrer_host);
+
+        let utm_source = clean(utm_source, true);
+        let campaign = (!utm_source.is_empty()).then(|| Campaign {
+            source: utm_source.clone(),
+            medium: clean(utm_medium, true),
+            campaign: clean(utm_campaign, false),
+        });
+
+        let source = match &host {
+            Some(host) => classify_host(host, site_hosts),
+            None if !utm_source.is_empty() => classify_name(&utm_source),
+            None => Source::Direct,
+        };
+
+        ViewOrigin { source, referrer_host: host.unwrap_or_default(), campaign }
+    }
+}
@@ -0,0 +1,13 @@
+use actix_web::web;
+use crate::stats::handlers;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(handlers::bot_traffic)
+        .service(handlers::site_stats)
+        .service(handlers::post_stats)
+        .service(handlers::referrer_report)
+        .service(handlers::campaign_report)
+        .service(handlers::engagement_report)
+        .service(handlers::export_posts)
+        .service(handlers::export_daily);
+}
@@ -0,0 +1,385 @@
+use chrono::{ Days, NaiveDate, Utc };
+use deadpool_postgres::GenericClient;
+
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::stats::dto::{
+    BotReasonHits,
+    BotTrafficReport,
+    CampaignHits,
+    CampaignReport,
+    DailyHits,
+    EngagementReport,
+    Granularity,
+    PostEngagement,
+    ReferrerHits,
+    ReferrerReport,
+    SourceHits,
+    StatsPoint,
+    TimeSeries,
+};
+use crate::stats::referrer::{ Campaign, ViewOrigin };
+
+/// 한 글의 오늘 통계에 조회, 방문자, 반응 수를 더합니다.
+pub async fn record_daily(
+    client: &impl GenericClient,
+    post_id: i32,
+    views: i32,
+    visitors: i32,
+    reactions: i32
+) -> Result<(), ServiceError> {
+    let stmt = client
+        .prepare_cached(
+            "INSERT INTO post_daily_stats (day, post_id, views, visitors, reactions)
+             SELECT $1, id, $3, $4, $5 FROM posts WHERE id = $2
+             ON CONFLICT (day, post_id) DO UPDATE SET
+                 views     = post_daily_stats.views + EXCLUDED.views,
+                 visitors  = post_daily_stats.visitors + EXCLUDED.visitors,
+                 reactions = post_daily_stats.reactions + EXCLUDED.reactions"
+        ).await?;
+    client.execute(&stmt, &[&Utc::now().date_naive(), &post_id, &views, &visitors, &reactions]).await?;
+
+    Ok(())
+}
+
+/// 버퍼에 모인 조회 수를 여러 글에 걸쳐 한 번에 더합니다.
+pub async fn record_daily_batch(
+    client: &impl GenericClient,
+    day: NaiveDate,
+    post_ids: &[i32],
+    views: &[i32],
+    visitors: &[i32]
+) -> Result<(), ServiceError> {
+    let stmt = client
+        .prepare_cached(
+            "INSERT INTO post_daily_stats (day, post_id, views, visitors)
+             SELECT $1, v.id, v.views, v.visitors
+             FROM unnest($2::int[], $3::int[], $4::int[]) AS v(id, views, visitors)
+             JOIN posts ON posts.id = v.id
+             ON CONFLICT (day, post_id) DO UPDATE SET
+                 views    = post_daily_stats.views + EXCLUDED.views,
+                 visitors = post_daily_stats.visitors + EXCLUDED.visitors"
+        ).await?;
+    client.execute(&stmt, &[&day, &post_ids, &views, &visitors]).await?;
+
+    Ok(())
+}
+
+/// 글마다 유입 경로와 캠페인별 방문 수를 오늘 날짜로 더합니다.
+pub async fn record_origins(
+    client: &impl GenericClient,
+    day: NaiveDate,
+    entries: &[(i32, ViewOrigin, i32)]
+) -> Result<(), ServiceError> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let post_ids: Vec<i32> = entries.iter().map(|(id, _, _)| *id).collect();
+    let sources: Vec<&str> = entries.iter().map(|(_, o, _)| o.source.as_str()).collect();
+    let hosts: Vec<&str> = entries.iter().map(|(_, o, _)| o.referrer_host.as_str()).collect();
+    let hits: Vec<i32> = entries.iter().map(|(_, _, n)| *n).collect();
+
+    let stmt = client
+        .prepare_cached(
+            "INSERT INTO view_sources (day, post_id, source, referrer_host, hits)
+             SELECT $1, v.id, v.source, v.host, SUM(v.hits)
+             FROM unnest($2::int[], $3::text[], $4::text[], $5::int[]) AS v(id, source, host, hits)
+             JOIN posts ON posts.id = v.id
+             GROUP BY v.id, v.source, v.host
+             ON CONFLICT (day, post_id, source, referrer_host) DO UPDATE SET
+                 hits = view_sources.hits + EXCLUDED.hits"
+        ).await?;
+    client.execute(&stmt, &[&day, &post_ids, &sources, &hosts, &hits]).await?;
+
+    let campaigns: Vec<(i32, &Campaign, i32)> = entries
+        .iter()
+        .filter_map(|(id, o, n)| o.campaign.as_ref().map(|c| (*id, c, *n)))
+        .collect();
+    if campaigns.is_empty() {
+        return Ok(());
+    }
+
+    let post_ids: Vec<i32> = campaigns.iter().map(|(id, _, _)| *id).collect();
+    let utm_sources: Vec<&str> = campaigns.iter().map(|(_, c, _)| c.source.as_str()).collect();
+    let utm_mediums: Vec<&str> = campaigns.iter().map(|(_, c, _)| c.medium.as_str()).collect();
+    let utm_campaigns: Vec<&str> = campaigns.iter().map(|(_, c, _)| c.campaign.as_str()).collect();
+    let hits: Vec<i32> = campaigns.iter().map(|(_, _, n)| *n).collect();
+
+    let stmt = client
+        .prepare_cached(
+            "INSERT INTO view_campaigns (day, post_id, utm_source, utm_medium, utm_campaign, hits)
+             SELECT $1, v.id, v.src, v.medium, v.campaign, SUM(v.hits)
+             FROM unnest($2::int[], $3::text[], $4::text[], $5::text[], $6::int[])
+                  AS v(id, src, medium, campaign, hits)
+             JOIN posts ON posts.id = v.id
+             GROUP BY v.id, v.src, v.medium, v.campaign
+             ON CONFLICT (day, post_id, utm_source, utm_medium, utm_campaign) DO UPDATE SET
+                 hits = view_campaigns.hits + EXCLUDED.hits"
+        ).await?;
+    client.execute(&stmt, &[&day, &post_ids, &utm_sources, &utm_mediums, &utm_campaigns, &hits]).await?;
+
+    Ok(())
+}
+
+/// 글이 없으면 `NotFound` 를 돌려줍니다.
+pub async fn record_engagement(
+    client: &impl GenericClient,
+    day: NaiveDate,
+    post_id: i32,
+    read_secs: i64,
+    completed: bool
+) -> Result<(), ServiceError> {
+    let stmt = client
+        .prepare_cached(
+            "INSERT INTO post_engagement (day, post_id, reads, read_secs, completions)
+             SELECT $1, id, 1, $3, $4 FROM posts WHERE id = $2
+             ON CONFLICT (day, post_id) DO UPDATE SET
+                 reads = post_engagement.reads + 1,
+                 read_secs = post_engagement.read_secs + EXCLUDED.read_secs,
+                 completions = post_engagement.completions + EXCLUDED.completions"
+        ).await?;
+    let inserted = client.execute(&stmt, &[&day, &post_id, &read_secs, &i32::from(completed)]).await?;
+    if inserted == 0 {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(())
+}
+
+pub async fn engagement_report(
+    pool: &DbPool,
+    post_id: Option<i32>,
+    from: NaiveDate,
+    to: NaiveDate,
+    limit: i64
+) -> Result<EngagementReport, ServiceError> {
+    let client = pool.get().await?;
+
+    let rows = client
+        .query(
+            "SELECT e.post_id, p.title, SUM(e.reads)::bigint, SUM(e.read_secs)::bigint, SUM(e.completions)::bigint
+             FROM post_engagement e
+             JOIN posts p ON p.id = e.post_id
+             WHERE e.day BETWEEN $1 AND $2 AND ($3::int IS NULL OR e.post_id = $3)
+             GROUP BY e.post_id, p.title
+             ORDER BY 3 DESC, e.post_id",
+            &[&from, &to, &post_id]
+        ).await?;
+
+    let (mut reads, mut read_secs, mut completions) = (0, 0, 0);
+    let mut posts = Vec::with_capacity(rows.len());
+    for row in rows {
+        let post_reads: i64 = row.get(2);
+        let post_secs: i64 = row.get(3);
+        let post_completions: i64 = row.get(4);
+        reads += post_reads;
+        read_secs += post_secs;
+        completions += post_completions;
+        posts.push(PostEngagement {
+            post_id: row.get(0),
+            title: row.get(1),
+            reads: post_reads,
+            avg_read_secs: ratio(post_secs, post_reads),
+            completion_rate: ratio(post_completions, post_reads),
+        });
+    }
+    posts.truncate(limit.max(0) as usize);
+
+    Ok(EngagementReport {
+        post_id,
+        from,
+        to,
+        reads,
+        avg_read_secs: ratio(read_secs, reads),
+        completion_rate: ratio(completions, reads),
+        posts,
+    })
+}
+
+fn ratio(part: i64, whole: i64) -> f64 {
+    if whole == 0 { 0.0 } else { part as f64 / whole as f64 }
+}
+
+pub async fn referrer_report(
+    pool: &DbPool,
+    post_id: Option<i32>,
+    from: NaiveDate,
+    to: NaiveDate,
+    limit: i64
+) -> Result<ReferrerReport, ServiceError> {
+    let client = pool.get().await?;
+
+    let rows = client
+        .query(
+            "SELECT source, SUM(hits)::bigint AS hits FROM view_sources
+             WHERE day BETWEEN $1 AND $2 AND ($3::int IS NULL OR post_id = $3)
+             GROUP BY source
+             ORDER BY hits DESC, source",
+            &[&from, &to, &post_id]
+        ).await?;
+    let sources: Vec<SourceHits> = rows
+        .into_iter()
+        .map(|row| SourceHits { source: row.get(0), hits: row.get(1) })
+        .collect();
+
+    let rows = client
+        .query(
+            "SELECT referrer_host, source, SUM(hits)::bigint AS hits FROM view_sources
+             WHERE day BETWEEN $1 AND $2 AND ($3::int IS NULL OR post_id = $3) AND referrer_host <> ''
+             GROUP BY referrer_host, source
+             ORDER BY hits DESC, referrer_host
+             LIMIT $4",
+            &[&from, &to, &post_id, &limit]
+        ).await?;
+    let referrers: Vec<ReferrerHits> = rows
+        .into_iter()
+        .map(|row| ReferrerHits { host: row.get(0), source: row.get(1), hits: row.get(2) })
+        .collect();
+
+    Ok(ReferrerReport { post_id, from, to, sources, referrers })
+}
+
+pub async fn campaign_report(
+    pool: &DbPool,
+    post_id: Option<i32>,
+    from: NaiveDate,
+    to: NaiveDate,
+    limit: i64
+) -> Result<CampaignReport, ServiceError> {
+    let client = pool.get().await?;
+
+    let rows = client
+        .query(
+            "SELECT utm_source, utm_medium, utm_campaign, SUM(hits)::bigint AS hits FROM view_campaigns
+             WHERE day BETWEEN $1 AND $2 AND ($3::int IS NULL OR post_id = $3)
+             GROUP BY utm_source, utm_medium, utm_campaign
+             ORDER BY hits DESC, utm_source, utm_medium, utm_campaign
+             LIMIT $4",
+            &[&from, &to, &post_id, &limit]
+        ).await?;
+    let campaigns: Vec<CampaignHits> = rows
+        .into_iter()
+        .map(|row| CampaignHits {
+            utm_source: row.get(0),
+            utm_medium: row.get(1),
+            utm_campaign: row.get(2),
+            hits: row.get(3),
+        })
+        .collect();
+
+    Ok(CampaignReport { post_id, from, to, campaigns })
+}
+
+/// `post_id` 가 없으면 사이트 전체를 합산합니다. 기록이 없는 구간도 0 으로 채워 돌려줍니다.
+pub async fn time_series(
+    pool: &DbPool,
+    post_id: Option<i32>,
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: Granularity
+) -> Result<TimeSeries, ServiceError> {
+    let client = pool.get().await?;
+
+    if let Some(post_id) = post_id {
+        client
+            .query_opt("SELECT 1 FROM posts WHERE id = $1", &[&post_id]).await?
+            .ok_or(ServiceError::NotFound)?;
+    }
+
+    let stmt = client
+        .prepare_cached(
+            "SELECT p.period::date,
+                    COALESCE(SUM(s.views), 0)::bigint,
+                    COALESCE(SUM(s.visitors), 0)::bigint,
+                    COALESCE(SUM(s.reactions), 0)::bigint
+             FROM generate_series(
+                      date_trunc($1, $2::date::timestamp),
+                      date_trunc($1, $3::date::timestamp),
+                      ('1 ' || $1)::interval
+                  ) AS p(period)
+             LEFT JOIN post_daily_stats s
+                    ON date_trunc($1, s.day::timestamp) = p.period
+                   AND s.day BETWEEN $2 AND $3
+                   AND ($4::int IS NULL OR s.post_id = $4)
+             GROUP BY p.period
+             ORDER BY p.period"
+        ).await?;
+    let rows = client.query(&stmt, &[&granularity.as_str(), &from, &to, &post_id]).await?;
+
+    let points: Vec<StatsPoint> = rows
+        .into_iter()
+        .map(|row| StatsPoint {
+            period: row.get(0),
+            views: row.get(1),
+            visitors: row.get(2),
+            reactions: row.get(3),
+        })
+        .collect();
+
+    Ok(TimeSeries {
+        post_id,
+        from,
+        to,
+        granularity,
+        total_views: points.iter().map(|p| p.views).sum(),
+        total_visitors: points.iter().map(|p| p.visitors).sum(),
+        total_reactions: points.iter().map(|p| p.reactions).sum(),
+        points,
+    })
+}
+
+/// 봇 조회는 조회수에 넣지 않고 날짜·사유별로 따로 셉니다. 없거나 발행되지 않은 글이면 `NotFound` 입니다.
+pub async fn record_bot_hit(pool: &DbPool, post_id: i32, reason: &str) -> Result<(), ServiceError> {
+    let client = pool.get().await?;
+
+    let stmt = client
+        .prepare_cached(
+            "INSERT INTO bot_hits (day, post_id, reason, hits)
+             SELECT $1, id, $3, 1 FROM posts WHERE id = $2 AND status = 'published'
+             ON CONFLICT (day, post_id, reason) DO UPDATE SET hits = bot_hits.hits + 1"
+        ).await?;
+    let recorded = client.execute(&stmt, &[&Utc::now().date_naive(), &post_id, &reason]).await?;
+    if recorded == 0 {
+        return Err(ServiceError::NotFound);
+    }
+
+    Ok(())
+}
+
+pub async fn bot_traffic(pool: &DbPool, days: u32) -> Result<BotTrafficReport, ServiceError> {
+    let client = pool.get().await?;
+    let since = Utc::now().date_naive() - Days::new(u64::from(days.saturating_sub(1)));
+
+    let rows = client
+        .query(
+            "SELECT reason, SUM(hits)::bigint AS hits FROM bot_hits
+             WHERE day >= $1
+             GROUP BY reason
+             ORDER BY hits DESC, reason",
+            &[&since]
+        ).await?;
+    let by_reason: Vec<BotReasonHits> = rows
+        .into_iter()
+        .map(|row| BotReasonHits { reason: row.get(0), hits: row.get(1) })
+        .collect();
+
+    let rows = client
+        .query(
+            "SELECT day, SUM(hits)::bigint FROM bot_hits
+             WHERE day >= $1
+             GROUP BY day
+             ORDER BY day",
+            &[&since]
+        ).await?;
+    let by_day: Vec<DailyHits> = rows
+        .into_iter()
+        .map(|row| DailyHits { day: row.get(0), hits: row.get(1) })
+        .collect();
+
+    Ok(BotTrafficReport {
+        days,
+        total_hits: by_reason.iter().map(|r| r.hits).sum(),
+        by_reason,
+        by_day,
+    })
+}
@@ -0,0 +1,175 @@
+use std::collections::{ HashMap, HashSet };
+use std::sync::Mutex;
+use std::time::Duration;
+
+use actix_web::web;
+use chrono::Utc;
+
+use crate::config::StatsConfig;
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::stats::referrer::ViewOrigin;
+use crate::stats::service as stats_service;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Pending {
+    /// 중복을 뺀 방문 수. 글의 조회수에 더해집니다.
+    visitors: i32,
+    /// 같은 방문자의 재방문까지 포함한 조회 수
+    views: i32,
+}
+
+#[derive(Default)]
+struct Counters {
+    /// 아직 DB 에 반영하지 않은 증가분
+    pending: HashMap<i32, Pending>,
+    /// 처음 본 방문자의 유입 경로별 수
+    origins: HashMap<(i32, ViewOrigin), i32>,
+    /// 마지막으로 확인한 DB 의 조회수
+    known: HashMap<i32, i32>,
+}
+
+/// 조회수 증가를 글마다 메모리에 모았다가 주기적으로 한 번에 반영합니다.
+/// 인기 글의 행 잠금 경합을 피하는 대신, 응답하는 조회수는 근사치입니다.
+pub struct ViewBuffer {
+    buffered: bool,
+    counters: Mutex<Counters>,
+}
+
+impl ViewBuffer {
+    pub fn new(cfg: &StatsConfig) -> Self {
+        ViewBuffer { buffered: cfg.buffer_views, counters: Mutex::new(Counters::default()) }
+    }
+
+    pub fn is_buffered(&self) -> bool {
+        self.buffered
+    }
+
+    /// 조회 한 번을 쌓고 DB 조회수와 쌓인 증가분을 더한 근사치를 돌려줍니다.
+    pub async fn add(&self, pool: &DbPool, post_id: i32, origin: ViewOrigin) -> Result<i32, ServiceError> {
+        if let Some(count) = self.try_add(post_id, &origin) {
+            return Ok(count);
+        }
+
+        let client = pool.get().await?;
+        let row = client
+            .query_opt("SELECT view_count FROM posts WHERE id = $1", &[&post_id]).await?
+            .ok_or(ServiceError::NotFound)?;
+
+        let mut counters = self.counters.lock().unwrap();
+        counters.known.entry(post_id).or_insert(row.get(0));
+        Ok(self.increment(&mut counters, post_id, origin))
+    }
+
+    fn try_add(&self, post_id: i32, origin: &ViewOrigin) -> Option<i32> {
+        let mut counters = self.counters.lock().unwrap();
+        if !counters.known.contains_key(&post_id) {
+            return None;
+        }
+        Some(self.increment(&mut counters, post_id, origin.clone()))
+    }
+
+    fn increment(&self, counters: &mut Counters, post_id: i32, origin: ViewOrigin) -> i32 {
+        *counters.origins.entry((post_id, origin)).or_default() += 1;
+        let pending = counters.pending.entry(post_id).or_default();
+        pending.visitors += 1;
+        pending.views += 1;
+        let visitors = pending.visitors;
+        counters.known[&post_id] + visitors
+    }
+
+    /// 오늘 이미 센 방문자의 재조회. 조회수는 그대로 두고 일별 통계에만 더합니다.
+    pub fn add_repeat(&self, post_id: i32) {
+        let mut counters = self.counters.lock().unwrap();
+        counters.pending.entry(post_id).or_default().views += 1;
+    }
+
+    /// 쌓인 증가분을 글 조회수와 일별 통계에 한 번에 반영합니다. 실패하면 증가분을 되돌려 다음 번에 다시 시도합니다.
+    pub async fn flush(&self, pool: &DbPool) -> Result<usize, ServiceError> {
+        let (pending, origins) = {
+            let mut counters = self.counters.lock().unwrap();
+            (std::mem::take(&mut counters.pending), std::mem::take(&mut counters.origins))
+        };
+        if pending.is_empty() {
+            return Ok(0);
+        }
+        let origins: Vec<(i32, ViewOrigin, i32)> = origins
+            .into_iter()
+            .map(|((id, origin), hits)| (id, origin, hits))
+            .collect();
+
+        let mut ids = Vec::with_capacity(pending.len());
+        let mut visitors = Vec::with_capacity(pending.len());
+        let mut views = Vec::with_capacity(pending.len());
+        for (id, p) in &pending {
+            ids.push(*id);
+            visitors.push(p.visitors);
+            views.push(p.views);
+        }
+
+        let result = async {
+            let mut client = pool.get().await?;
+            let tx = client.transaction().await?;
+
+            let stmt = tx
+                .prepare_cached(
+                    "UPDATE posts SET view_count = posts.view_count + v.visitors
+                     FROM unnest($1::int[], $2::int[]) AS v(id, visitors)
+                     WHERE posts.id = v.id AND v.visitors > 0
+                     RETURNING posts.id, posts.view_count"
+                ).await?;
+            let rows = tx.query(&stmt, &[&ids, &visitors]).await?;
+
+            let day = Utc::now().date_naive();
+            stats_service::record_daily_batch(&tx, day, &ids, &views, &visitors).await?;
+            stats_service::record_origins(&tx, day, &origins).await?;
+            tx.commit().await?;
+            Ok::<_, ServiceError>(rows)
+        }.await;
+
+        let mut counters = self.counters.lock().unwrap();
+        match result {
+            Ok(rows) => {
+                // 반영하는 동안 새로 쌓인 증가분은 pending 에 남아 있으니 DB 값만 바꾸면 됩니다.
+                let mut updated = HashSet::new();
+                for row in rows {
+                    let id: i32 = row.get(0);
+                    counters.known.insert(id, row.get(1));
+                    updated.insert(id);
+                }
+                // 그 사이 지워진 글은 더 셀 필요가 없습니다.
+                for (id, p) in &pending {
+                    if p.visitors > 0 && !updated.contains(id) {
+                        counters.known.remove(id);
+                        counters.pending.remove(id);
+                        counters.origins.retain(|(origin_id, _), _| origin_id != id);
+                    }
+                }
+                Ok(pending.len())
+            }
+            Err(e) => {
+                for (id, p) in pending {
+                    let entry = counters.pending.entry(id).or_default();
+                    entry.visitors += p.visitors;
+                    entry.views += p.views;
+                }
+                for (id, origin, hits) in origins {
+                    *counters.origins.entry((id, origin)).or_default() += hits;
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+pub async fn run_view_flush(views: web::Data<ViewBuffer>, pool: DbPool, cfg: StatsConfig) {
+    let mut interval = actix_web::rt::time::interval(Duration::from_secs(cfg.view_flush_interval_secs.max(1)));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = views.flush(&pool).await {
+            tracing::error!("조회수 반영 실패: {e}");
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::NaiveDateTime;
+use serde::{ Deserialize, Serialize };
+
+use crate::blog::model::PostSummary;
+use crate::tag::model::Tag;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTag {
+    /// 바꾸면 이 태그가 달린 모든 글에 반영됩니다.
+    pub slug: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeTag {
+    /// 합쳐질 대상 태그
+    pub into: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagSort {
+    #[default]
+    Name,
+    /// 글이 많은 순
+    Count,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagSummary {
+    #[serde(flatten)]
+    pub tag: Tag,
+    pub post_count: i64,
+    pub latest_post_at: Option<NaiveDateTime>,
+}
+
+/// 태그 페이지. 다음 페이지는 `/posts?tag=` 로 이어서 받습니다.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagDetail {
+    #[serde(flatten)]
+    pub summary: TagSummary,
+    pub posts: Vec<PostSummary>,
+}
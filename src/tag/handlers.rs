@@ -0,0 +1,104 @@
+use actix_web::{ delete, get, post, put, web, HttpResponse, Responder, ResponseError };
+use serde::Deserialize;
+
+use crate::blog::related::RelatedPosts;
+use crate::db::DbPool;
+use crate::tag::dto::{ MergeTag, TagSort, UpdateTag };
+use crate::tag::service;
+use crate::user::handlers::Admin;
+
+const MAX_TAG_LIMIT: u32 = 500;
+
+#[derive(Debug, Deserialize)]
+struct TagListQuery {
+    #[serde(default)]
+    sort: TagSort,
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagPageQuery {
+    #[serde(rename = "pageSize")]
+    page_size: Option<u32>,
+}
+
+#[get("/tags")]
+pub async fn list_tags(pool: web::Data<DbPool>, web::Query(query): web::Query<TagListQuery>) -> impl Responder {
+    let limit = query.limit.map(|limit| limit.clamp(1, MAX_TAG_LIMIT) as i64);
+
+    match service::list(&pool, query.sort, limit).await {
+        Ok(tags) => { HttpResponse::Ok().json(tags) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[get("/tags/{slug}")]
+pub async fn get_tag(
+    pool: web::Data<DbPool>,
+    path: web::Path<String>,
+    web::Query(query): web::Query<TagPageQuery>
+) -> impl Responder {
+    let slug = path.into_inner();
+    let page_size = query.page_size.unwrap_or(8).clamp(1, 50) as i64;
+
+    match service::get_detail(&pool, &slug, page_size).await {
+        Ok(tag) => { HttpResponse::Ok().json(tag) }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[put("/admin/tags/{slug}")]
+pub async fn update_tag(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    related: web::Data<RelatedPosts>,
+    path: web::Path<String>,
+    web::Json(dto): web::Json<UpdateTag>
+) -> impl Responder {
+    let slug = path.into_inner();
+
+    match service::update(&pool, &slug, dto).await {
+        Ok(tag) => {
+            related.invalidate();
+            HttpResponse::Ok().json(tag)
+        }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[post("/admin/tags/{slug}/merge")]
+pub async fn merge_tag(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    related: web::Data<RelatedPosts>,
+    path: web::Path<String>,
+    web::Json(dto): web::Json<MergeTag>
+) -> impl Responder {
+    let slug = path.into_inner();
+
+    match service::merge(&pool, &slug, &dto.into).await {
+        Ok(tag) => {
+            related.invalidate();
+            HttpResponse::Ok().json(tag)
+        }
+        Err(e) => { e.error_response() }
+    }
+}
+
+#[delete("/admin/tags/{slug}")]
+pub async fn delete_tag(
+    _: Admin,
+    pool: web::Data<DbPool>,
+    related: web::Data<RelatedPosts>,
+    path: web::Path<String>
+) -> impl Responder {
+    let slug = path.into_inner();
+
+    match service::delete(&pool, &slug).await {
+        Ok(_) => {
+            related.invalidate();
+            HttpResponse::NoContent().finish()
+        }
+        Err(e) => { e.error_response() }
+    }
+}
@@ -0,0 +1,16 @@
+use chrono::NaiveDateTime;
+use serde::{ Deserialize, Serialize };
+use tokio_pg_mapper_derive::PostgresMapper;
+
+/// 글의 `tags` 배열에는 `slug` 만 저장하고, 표시용 정보는 여기에 둡니다.
+#[derive(Debug, Clone, Serialize, Deserialize, PostgresMapper)]
+#[pg_mapper(table = "tags")]
+pub struct Tag {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    /// `#rrggbb` 형식. 지정하지 않았으면 빈 문자열입니다.
+    pub color: String,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,10 @@
+use actix_web::web;
+use crate::tag::handlers;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(handlers::list_tags)
+        .service(handlers::get_tag)
+        .service(handlers::update_tag)
+        .service(handlers::merge_tag)
+        .service(handlers::delete_tag);
+}
@@ -0,0 +1,256 @@
+use deadpool_postgres::GenericClient;
+use tokio_pg_mapper::FromTokioPostgresRow;
+
+use crate::blog::dto::PostFilter;
+use crate::blog::service as blog_service;
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+use crate::slug::slug_conflict;
+use crate::tag::dto::{ TagDetail, TagSort, TagSummary, UpdateTag };
+use crate::tag::model::Tag;
+
+const TAG_COLUMNS: &str = "id, slug, name, description, color, created_at";
+
+const MAX_TAG_CHARS: usize = 50;
+const MAX_POST_TAGS: usize = 20;
+const MAX_DESCRIPTION_CHARS: usize = 500;
+
+/// 대소문자나 띄어쓰기 차이로 같은 태그가 갈라지지 않도록 슬러그로 맞춥니다.
+/// 공백, 밑줄, 쉼표, 슬래시는 하이픈 하나로 바꾸고 나머지 문자(`c#`, `node.js`, 한글 등)는 그대로 둡니다.
+/// `sql/migrations/014_tags.sql` 의 `normalize_tag` 와 규칙이 같아야 합니다.
+pub fn normalize(raw: &str) -> Result<String, ServiceError> {
+    let mut slug = String::new();
+    for c in raw.trim().chars() {
+        if c.is_whitespace() || c.is_control() || matches!(c, '-' | '_' | ',' | '/') {
+            if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        } else {
+            slug.extend(c.to_lowercase());
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+
+    if slug.is_empty() {
+        return Err(ServiceError::BadRequest("빈 태그는 쓸 수 없습니다".into()));
+    }
+    if slug.chars().count() > MAX_TAG_CHARS {
+        return Err(ServiceError::BadRequest(format!("태그는 {}자를 넘을 수 없습니다", MAX_TAG_CHARS)));
+    }
+    Ok(slug.to_string())
+}
+
+fn validate_name(name: &str) -> Result<String, ServiceError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ServiceError::BadRequest("태그 이름을 입력해주세요".into()));
+    }
+    if name.chars().count() > MAX_TAG_CHARS {
+        return Err(ServiceError::BadRequest(format!("태그 이름은 {}자를 넘을 수 없습니다", MAX_TAG_CHARS)));
+    }
+    Ok(name.to_string())
+}
+
+fn validate_color(color: &str) -> Result<String, ServiceError> {
+    let color = color.trim().to_lowercase();
+    let valid = color.is_empty() ||
+        (color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit()));
+    if !valid {
+        return Err(ServiceError::BadRequest("색상은 #rrggbb 형식이어야 합니다".into()));
+    }
+    Ok(color)
+}
+
+/// 글에 달 태그를 정규화하고, 처음 보는 태그는 입력한 표기를 이름으로 등록합니다.
+/// 같은 글 안의 중복은 처음 나온 순서대로 하나만 남깁니다.
+pub async fn register(client: &impl GenericClient, raw: &[String]) -> Result<Vec<String>, ServiceError> {
+    let mut slugs: Vec<String> = Vec::new();
+    let mut names: Vec<String> = Vec::new();
+    for tag in raw {
+        let slug = normalize(tag)?;
+        if !slugs.contains(&slug) {
+            slugs.push(slug);
+            names.push(tag.trim().chars().take(MAX_TAG_CHARS).collect());
+        }
+    }
+    if slugs.len() > MAX_POST_TAGS {
+        return Err(ServiceError::BadRequest(format!("태그는 글 하나에 {}개까지 달 수 있습니다", MAX_POST_TAGS)));
+    }
+
+    client
+        .execute(
+            "INSERT INTO tags (slug, name)
+             SELECT * FROM unnest($1::text[], $2::text[])
+             ON CONFLICT (slug) DO NOTHING",
+            &[&slugs, &names]
+        ).await?;
+
+    Ok(slugs)
+}
+
+fn summary(row: &tokio_postgres::Row) -> Result<TagSummary, ServiceError> {
+    Ok(TagSummary {
+        tag: Tag::from_row_ref(row)?,
+        post_count: row.get("post_count"),
+        latest_post_at: row.get("latest_post_at"),
+    })
+}
+
+/// 글이 하나 이상 달린 태그만 돌려줍니다.
+pub async fn list(pool: &DbPool, sort: TagSort, limit: Option<i64>) -> Result<Vec<TagSummary>, ServiceError> {
+    let client = pool.get().await?;
+
+    let order = match sort {
+        TagSort::Name => "t.name, t.slug",
+        TagSort::Count => "post_count DESC, t.name, t.slug",
+    };
+    let rows = client
+        .query(
+            &format!(
+                "SELECT t.id, t.slug, t.name, t.description, t.color, t.created_at,
+                        COUNT(*) AS post_count, MAX(p.created_at) AS latest_post_at
+                 FROM posts p, unnest(p.tags) AS tag
+                 JOIN tags t ON t.slug = tag
+                 WHERE p.status = 'published'
+                 GROUP BY t.id
+                 ORDER BY {order}
+                 LIMIT $1"
+            ),
+            &[&limit]
+        ).await?;
+
+    rows.iter().map(summary).collect()
+}
+
+pub async fn get_detail(pool: &DbPool, slug: &str, page_size: i64) -> Result<TagDetail, ServiceError> {
+    let slug = normalize(slug)?;
+    let client = pool.get().await?;
+
+    let row = client
+        .query_opt(
+            "SELECT t.id, t.slug, t.name, t.description, t.color, t.created_at,
+                    COUNT(p.id) AS post_count, MAX(p.created_at) AS latest_post_at
+             FROM tags t
+             LEFT JOIN posts p ON t.slug = ANY(p.tags) AND p.status = 'published'
+             WHERE t.slug = $1
+             GROUP BY t.id",
+            &[&slug]
+        ).await?
+        .ok_or(ServiceError::NotFound)?;
+    let summary = summary(&row)?;
+    drop(client);
+
+    let filter = PostFilter { tags: vec![summary.tag.slug.clone()], ..Default::default() };
+    let page = blog_service::list_all(pool, page_size, 0, filter).await?;
+
+    Ok(TagDetail { summary, posts: page.posts })
+}
+
+async fn lock(client: &impl GenericClient, slug: &str) -> Result<Tag, ServiceError> {
+    let row = client
+        .query_opt(&format!("SELECT {} FROM tags WHERE slug = $1 FOR UPDATE", TAG_COLUMNS), &[&slug]).await?
+        .ok_or(ServiceError::NotFound)?;
+    Ok(Tag::from_row_ref(&row)?)
+}
+
+/// 슬러그를 바꾸면 이 태그가 달린 글도 함께 고칩니다.
+/// 이미 있는 태그 이름으로 바꾸려면 `merge` 를 써야 합니다.
+pub async fn update(pool: &DbPool, slug: &str, dto: UpdateTag) -> Result<Tag, ServiceError> {
+    let slug = normalize(slug)?;
+    let new_slug = dto.slug.as_deref().map(normalize).transpose()?;
+    let name = dto.name.as_deref().map(validate_name).transpose()?;
+    let color = dto.color.as_deref().map(validate_color).transpose()?;
+    let description = dto.description.as_deref().map(str::trim);
+    if let Some(description) = description
+        && description.chars().count() > MAX_DESCRIPTION_CHARS
+    {
+        return Err(ServiceError::BadRequest(format!("설명은 {}자를 넘을 수 없습니다", MAX_DESCRIPTION_CHARS)));
+    }
+
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    let current = lock(&tx, &slug).await?;
+
+    if let Some(new_slug) = &new_slug
+        && *new_slug != current.slug
+    {
+        let taken = tx.query_opt("SELECT 1 FROM tags WHERE slug = $1", &[new_slug]).await?.is_some();
+        if taken {
+            return Err(ServiceError::BadRequest("이미 있는 태그입니다. 합치려면 병합을 사용해주세요".into()));
+        }
+        tx.execute(
+            "UPDATE posts SET tags = array_replace(tags, $1, $2) WHERE $1 = ANY(tags)",
+            &[&current.slug, new_slug]
+        ).await?;
+    }
+
+    let row = tx
+        .query_one(
+            &format!(
+                "UPDATE tags SET
+                     slug = COALESCE($1, slug),
+                     name = COALESCE($2, name),
+                     description = COALESCE($3, description),
+                     color = COALESCE($4, color)
+                 WHERE id = $5
+                 RETURNING {}",
+                TAG_COLUMNS
+            ),
+            &[&new_slug, &name, &description, &color, &current.id]
+        ).await
+        .map_err(slug_conflict)?;
+    tx.commit().await?;
+
+    Ok(Tag::from_row_ref(&row)?)
+}
+
+/// `source` 를 `into` 로 합칩니다. 두 태그가 모두 달린 글은 `into` 하나만 남깁니다.
+pub async fn merge(pool: &DbPool, source: &str, into: &str) -> Result<Tag, ServiceError> {
+    let source = normalize(source)?;
+    let into = normalize(into)?;
+    if source == into {
+        return Err(ServiceError::BadRequest("같은 태그끼리는 합칠 수 없습니다".into()));
+    }
+
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    // 반대 방향 병합과 교착되지 않도록 항상 같은 순서로 잠급니다.
+    tx.query(
+        "SELECT id FROM tags WHERE slug = ANY($1) ORDER BY id FOR UPDATE",
+        &[&vec![source.as_str(), into.as_str()]]
+    ).await?;
+    let source = lock(&tx, &source).await?;
+    let target = lock(&tx, &into).await?;
+
+    tx.execute(
+        "UPDATE posts SET tags = CASE
+             WHEN $2 = ANY(tags) THEN array_remove(tags, $1)
+             ELSE array_replace(tags, $1, $2)
+         END
+         WHERE $1 = ANY(tags)",
+        &[&source.slug, &target.slug]
+    ).await?;
+    tx.execute("DELETE FROM tags WHERE id = $1", &[&source.id]).await?;
+    tx.commit().await?;
+
+    Ok(target)
+}
+
+/// 태그를 지우고 모든 글에서 뗍니다.
+pub async fn delete(pool: &DbPool, slug: &str) -> Result<(), ServiceError> {
+    let slug = normalize(slug)?;
+
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    let deleted = tx.execute("DELETE FROM tags WHERE slug = $1", &[&slug]).await?;
+    if deleted == 0 {
+        return Err(ServiceError::NotFound);
+    }
+    tx.execute("UPDATE posts SET tags = array_remove(tags, $1) WHERE $1 = ANY(tags)", &[&slug]).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
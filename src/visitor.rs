@@ -0,0 +1,158 @@
+use std::collections::{ HashMap, VecDeque };
+use std::sync::Mutex;
+use std::time::{ Duration, Instant };
+
+use actix_web::{ http::header, HttpRequest };
+use chrono::{ NaiveDate, Utc };
+use deadpool_postgres::GenericClient;
+use rand::RngCore;
+use sha2::{ Digest, Sha256 };
+
+use crate::db::DbPool;
+use crate::errors::ServiceError;
+
+/// 프록시의 `X-Forwarded-For` 는 위조할 수 있으므로 실제 접속 주소를 씁니다.
+pub fn client_ip(req: &HttpRequest) -> String {
+    req.peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+pub fn user_agent(req: &HttpRequest) -> &str {
+    req.headers()
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+}
+
+/// IP 와 User-Agent 를 그날의 salt 와 함께 해시해 방문자를 구분합니다.
+/// salt 는 하루마다 새로 만들고 지난 salt 는 지우므로, 날짜가 바뀌면 이전 해시로 방문자를 추적할 수 없습니다.
+pub struct VisitorHasher {
+    today: Mutex<Option<(NaiveDate, String)>>,
+}
+
+impl Default for VisitorHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VisitorHasher {
+    pub fn new() -> Self {
+        VisitorHasher { today: Mutex::new(None) }
+    }
+
+    async fn salt(&self, pool: &DbPool, day: NaiveDate) -> Result<String, ServiceError> {
+        if let Some((cached_day, salt)) = self.today.lock().unwrap().as_ref()
+            && *cached_day == day
+        {
+            return Ok(salt.clone());
+        }
+
+        let mut bytes = [0u8; 32];
+        rand::rng().fill_bytes(&mut bytes);
+
+        let client = pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO visitor_salts (day, salt) VALUES ($1, $2) ON CONFLICT (day) DO NOTHING",
+                &[&day, &hex::encode(bytes)]
+            ).await?;
+        let salt: String = client
+            .query_one("SELECT salt FROM visitor_salts WHERE day = $1", &[&day]).await?
+            .get(0);
+
+        // 날짜가 바뀌었으니 지난 salt 와 중복 기록은 더 이상 쓸모가 없습니다.
+        client.execute("DELETE FROM visitor_salts WHERE day < $1", &[&day]).await?;
+        client.execute("DELETE FROM visitor_hits WHERE day < $1", &[&day]).await?;
+
+        *self.today.lock().unwrap() = Some((day, salt.clone()));
+        Ok(salt)
+    }
+
+    pub async fn fingerprint(&self, pool: &DbPool, req: &HttpRequest) -> Result<Fingerprint, ServiceError> {
+        let day = Utc::now().date_naive();
+        let salt = self.salt(pool, day).await?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(salt.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(client_ip(req).as_bytes());
+        hasher.update(b"\0");
+        hasher.update(user_agent(req).as_bytes());
+
+        Ok(Fingerprint { day, hash: hex::encode(hasher.finalize()) })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Fingerprint {
+    pub day: NaiveDate,
+    pub hash: String,
+}
+
+/// 오늘 이 방문자가 `scope` 로 처음 찾아왔으면 기록하고 `None` 을 돌려줍니다.
+/// 이미 기록이 있으면 그때 남긴 `visitor_id` 를 돌려줍니다.
+pub async fn record_hit(
+    client: &impl GenericClient,
+    scope: &str,
+    post_id: i32,
+    fingerprint: &Fingerprint,
+    visitor_id: &str
+) -> Result<Option<String>, ServiceError> {
+    let inserted = client
+        .execute(
+            "INSERT INTO visitor_hits (scope, post_id, fingerprint, visitor_id, day)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT DO NOTHING",
+            &[&scope, &post_id, &fingerprint.hash, &visitor_id, &fingerprint.day]
+        ).await?;
+    if inserted == 1 {
+        return Ok(None);
+    }
+
+    let row = client
+        .query_one(
+            "SELECT visitor_id FROM visitor_hits WHERE scope = $1 AND post_id = $2 AND fingerprint = $3",
+            &[&scope, &post_id, &fingerprint.hash]
+        ).await?;
+    Ok(Some(row.get(0)))
+}
+
+/// 키(IP 등)마다 최근 `window` 동안의 요청 수를 세는 메모리 기반 제한기입니다.
+pub struct RateLimiter {
+    limit: usize,
+    window: Duration,
+    recent: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl RateLimiter {
+    /// `limit` 이 0 이면 제한하지 않습니다.
+    pub fn new(limit: usize, window: Duration) -> Self {
+        RateLimiter { limit, window, recent: Mutex::new(HashMap::new()) }
+    }
+
+    /// 허용 범위 안이면 요청을 기록하고 `true` 를 돌려줍니다.
+    pub fn allow(&self, key: &str) -> bool {
+        if self.limit == 0 {
+            return true;
+        }
+
+        let now = Instant::now();
+        let mut recent = self.recent.lock().unwrap();
+
+        recent.retain(|_, hits| {
+            while hits.front().is_some_and(|t| now.duration_since(*t) >= self.window) {
+                hits.pop_front();
+            }
+            !hits.is_empty()
+        });
+
+        let hits = recent.entry(key.to_string()).or_default();
+        if hits.len() >= self.limit {
+            return false;
+        }
+        hits.push_back(now);
+        true
+    }
+}
@@ -1,11 +1,42 @@
+#![allow(clippy::unnecessary_mut_passed)]
+
+use std::sync::Arc;
+
 use actix_web::{ test, web, App };
 use actix_web::http::StatusCode;
-use blog::blog::handlers::list_posts;
+use actix_web::http::header;
+use blog::blog::handlers::{
+    create_post,
+    delete_post,
+    get_post,
+    get_reactions,
+    list_posts,
+    popular_posts,
+    post_neighbors,
+    record_engagement,
+    related_posts,
+    toggle_reaction,
+    trending_posts,
+    update_post,
+    view_post,
+};
+use blog::blog::related::RelatedPosts;
+use blog::blog::model::{ Post, PostStatus, PostSummary };
+use blog::comment::handlers::create_comment;
+use blog::comment::spam::SpamFilter;
 use blog::config::AppConfig;
 use blog::db;
-use blog::blog::dto::PostListResponse;
+use blog::media::storage::{ LocalStorage, Storage };
+use blog::stats::bot::BotDetector;
+use blog::stats::views::ViewBuffer;
+use blog::visitor::VisitorHasher;
+use blog::blog::dto::{ PostCursorPage, PostListResponse, PostNeighbors, ReactionSummary };
 use confik::{ Configuration, EnvSource };
 use dotenvy::dotenv;
+use serde_json::json;
+
+mod common;
+use common::admin_cookie;
 
 #[actix_web::test]
 async fn test_list_posts_success_flow() {
@@ -36,3 +67,541 @@ async fn test_list_posts_success_flow() {
     assert!(!resp_data.posts.is_empty(), "posts가 비어있으면 안 됩니다");
     assert!(resp_data.posts.len() <= 12, "posts 길이가 page_size(12)를 초과하면 안 됩니다");
 }
+
+#[actix_web::test]
+async fn test_list_posts_filter_flow() {
+    dotenv().ok();
+
+    let config = AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap();
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(pool.clone()))
+        .service(list_posts);
+    let app = test::init_service(app).await;
+
+    let list = |query: &str| test::TestRequest::get().uri(&format!("/posts?pageSize=50&{query}")).to_request();
+    let ids = |data: &PostListResponse| {
+        let mut ids: Vec<i32> = data.posts.iter().map(|p| p.id).collect();
+        ids.sort_unstable();
+        ids
+    };
+
+    let data: PostListResponse = test::call_and_read_body_json(&app, list("tags=rust,actix")).await;
+    assert_eq!(ids(&data), vec![1], "기본은 모든 태그가 달린 글입니다");
+
+    let data: PostListResponse = test::call_and_read_body_json(&app, list("tags=rust,actix&match=any")).await;
+    assert_eq!((data.total_count, ids(&data)), (5, vec![1, 5, 6, 13, 14]));
+
+    let data: PostListResponse = test::call_and_read_body_json(&app, list("tag=Rust&exclude=actix")).await;
+    assert_eq!(ids(&data), vec![5, 14]);
+
+    let data: PostListResponse = test::call_and_read_body_json(&app, list("tags=rust,actix&match=any&sort=title")).await;
+    let titles: Vec<&str> = data.posts.iter().map(|p| p.title.as_str()).collect();
+    let mut sorted = titles.clone();
+    sorted.sort_unstable();
+    assert_eq!(titles, sorted, "제목순이어야 합니다");
+
+    let data: PostListResponse = test::call_and_read_body_json(&app, list("sort=views")).await;
+    assert!(data.posts.windows(2).all(|w| w[0].view_count >= w[1].view_count), "조회수순이어야 합니다");
+
+    let data: PostListResponse = test::call_and_read_body_json(&app, list("sort=oldest")).await;
+    assert!(data.posts.windows(2).all(|w| (w[0].created_at, w[0].id) < (w[1].created_at, w[1].id)));
+
+    let day = data.posts[0].created_at.date();
+    let data: PostListResponse = test::call_and_read_body_json(&app, list(&format!("tags=rust&from={day}&to={day}"))).await;
+    assert!(ids(&data).contains(&1), "종료일 당일에 쓴 글도 포함해야 합니다");
+    let data: PostListResponse = test::call_and_read_body_json(&app, list(&format!("to={}", day.pred_opt().unwrap()))).await;
+    assert!(!ids(&data).contains(&1));
+
+    for query in [
+        "sort=random",
+        "match=some",
+        "tags=rust&exclude=Rust",
+        "tags=a,b,c,d,e,f,g,h,i,j,k",
+        "from=2024-02-01&to=2024-01-01",
+    ] {
+        let resp = test::call_service(&app, list(query)).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{query} 는 400 이어야 합니다");
+    }
+}
+
+#[actix_web::test]
+async fn test_list_posts_cursor_flow() {
+    dotenv().ok();
+
+    let config = AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap();
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(pool.clone()))
+        .service(list_posts);
+    let app = test::init_service(app).await;
+
+    let get = |query: String| test::TestRequest::get().uri(&format!("/posts?{query}")).to_request();
+
+    for sort in ["newest", "oldest"] {
+        let all: PostListResponse = test::call_and_read_body_json(&app, get(format!("sort={sort}&pageSize=100"))).await;
+        assert!(all.next_cursor.is_none(), "마지막 페이지에는 커서가 없어야 합니다");
+        let expected: Vec<i32> = all.posts.iter().map(|p| p.id).collect();
+
+        let first: PostListResponse = test::call_and_read_body_json(&app, get(format!("sort={sort}&pageSize=7"))).await;
+        let mut seen: Vec<i32> = first.posts.iter().map(|p| p.id).collect();
+        let mut cursor = first.next_cursor.expect("다음 페이지가 있으면 커서를 내려줘야 합니다");
+        loop {
+            let page: PostCursorPage = test::call_and_read_body_json(
+                &app,
+                get(format!("sort={sort}&pageSize=7&cursor={cursor}"))
+            ).await;
+            assert!(page.posts.len() <= 7);
+            seen.extend(page.posts.iter().map(|p| p.id));
+            match page.next_cursor {
+                Some(next) => cursor = next,
+                None => break,
+            }
+        }
+        assert_eq!(seen, expected, "{sort}: 커서로 이어 받은 결과가 한 번에 받은 결과와 같아야 합니다");
+    }
+
+    let first: PostListResponse = test::call_and_read_body_json(&app, get("tags=rust&pageSize=2".to_string())).await;
+    let cursor = first.next_cursor.unwrap();
+    let page: PostCursorPage = test::call_and_read_body_json(&app, get(format!("tags=rust&pageSize=2&cursor={cursor}"))).await;
+    assert_eq!(page.posts.len(), 1, "커서 방식에도 필터가 적용되어야 합니다");
+    assert!(page.next_cursor.is_none());
+
+    let data: PostListResponse = test::call_and_read_body_json(&app, get("sort=views&pageSize=2".to_string())).await;
+    assert!(data.next_cursor.is_none(), "작성 시각 순서가 아니면 커서를 내려주지 않습니다");
+
+    for query in [
+        format!("sort=oldest&cursor={cursor}"),
+        format!("sort=title&cursor={cursor}"),
+        "cursor=not-a-cursor".to_string(),
+    ] {
+        let resp = test::call_service(&app, get(query.clone())).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{query} 는 400 이어야 합니다");
+    }
+}
+
+fn unique_user_agent() -> String {
+    format!("blog-test/{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default())
+}
+
+#[actix_web::test]
+async fn test_post_reactions_flow() {
+    dotenv().ok();
+
+    let config = AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap();
+
+    let pool = db::init_pool(&config.pg);
+    let post_id = 2;
+    let agent = unique_user_agent();
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(VisitorHasher::new()))
+        .service(list_posts)
+        .service(get_post)
+        .service(get_reactions)
+        .service(toggle_reaction);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{post_id}/reactions"))
+        .insert_header((header::USER_AGENT, agent.as_str()))
+        .set_json(serde_json::json!({ "emoji": "👍" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let visitor = resp
+        .response()
+        .cookies()
+        .find(|c| c.name() == "visitor_id")
+        .expect("처음 반응한 방문자에게 visitor_id 쿠키를 발급해야 합니다")
+        .into_owned();
+    let reactions: Vec<ReactionSummary> = test::read_body_json(resp).await;
+    let emojis: Vec<&str> = reactions.iter().map(|r| r.emoji.as_str()).collect();
+    assert_eq!(emojis, config.reaction.emojis(), "설정된 반응이 순서대로 모두 내려와야 합니다");
+    assert!(reactions[0].reacted);
+    assert_eq!(reactions[0].count, 1);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{post_id}/reactions"))
+        .insert_header((header::USER_AGENT, agent.as_str()))
+        .set_json(serde_json::json!({ "emoji": "👍" }))
+        .to_request();
+    let reactions: Vec<ReactionSummary> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(reactions[0].count, 1, "쿠키를 지우고 다시 눌러도 같은 방문자의 반응은 한 번만 세야 합니다");
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{post_id}/reactions"))
+        .insert_header((header::USER_AGENT, agent.as_str()))
+        .cookie(visitor.clone())
+        .set_json(serde_json::json!({ "emoji": "🎉" }))
+        .to_request();
+    let reactions: Vec<ReactionSummary> = test::call_and_read_body_json(&app, req).await;
+    assert!(reactions.iter().filter(|r| r.reacted).count() == 2, "여러 반응을 함께 남길 수 있어야 합니다");
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{post_id}/reactions"))
+        .insert_header((header::USER_AGENT, agent.as_str())).to_request();
+    let reactions: Vec<ReactionSummary> = test::call_and_read_body_json(&app, req).await;
+    assert!(reactions.iter().all(|r| !r.reacted), "다른 방문자에게는 반응하지 않은 상태로 보여야 합니다");
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{post_id}")).to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(post.reactions.0.get("👍"), Some(&1));
+    assert_eq!(post.reactions.0.get("🎉"), Some(&1));
+
+    let client = pool.get().await.unwrap();
+    let total: i32 = client
+        .query_one("SELECT reaction_total FROM posts WHERE id = $1", &[&post_id])
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(total, 2, "반응 합계는 반응과 같은 문장에서 갱신되어야 합니다");
+
+    let req = test::TestRequest::get().uri("/posts?sort=likes&pageSize=1000").to_request();
+    let data: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    let totals: Vec<i64> = data.posts.iter().map(|p| p.reactions.0.values().sum()).collect();
+    assert!(totals.windows(2).all(|w| w[0] >= w[1]), "좋아요순은 반응 합계가 많은 글부터 나와야 합니다: {totals:?}");
+
+    for emoji in ["👍", "🎉"] {
+        let req = test::TestRequest::post()
+            .uri(&format!("/posts/{post_id}/reactions"))
+            .insert_header((header::USER_AGENT, agent.as_str()))
+            .cookie(visitor.clone())
+            .set_json(serde_json::json!({ "emoji": emoji }))
+            .to_request();
+        let reactions: Vec<ReactionSummary> = test::call_and_read_body_json(&app, req).await;
+        let reaction = reactions.iter().find(|r| r.emoji == emoji).unwrap();
+        assert!(!reaction.reacted, "같은 반응을 다시 누르면 취소되어야 합니다");
+        assert_eq!(reaction.count, 0);
+    }
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{post_id}")).to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    assert!(post.reactions.0.is_empty(), "0 이 된 반응은 남지 않아야 합니다");
+    let total: i32 = client
+        .query_one("SELECT reaction_total FROM posts WHERE id = $1", &[&post_id])
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(total, 0);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{post_id}/reactions"))
+        .insert_header((header::USER_AGENT, agent.as_str()))
+        .set_json(serde_json::json!({ "emoji": "💩" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "설정에 없는 반응은 거절해야 합니다");
+}
+
+#[actix_web::test]
+async fn test_view_dedup_flow() {
+    dotenv().ok();
+
+    let mut config = AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap();
+    config.stats.buffer_views = false;
+
+    let pool = db::init_pool(&config.pg);
+    let post_id = 3;
+    let agent = unique_user_agent();
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(VisitorHasher::new()))
+        .app_data(web::Data::new(BotDetector::new(&config.stats)))
+        .app_data(web::Data::new(ViewBuffer::new(&config.stats)))
+        .service(view_post);
+
+    let app = test::init_service(app).await;
+
+    let view = |agent: &str| {
+        test::TestRequest::post()
+            .uri(&format!("/posts/{post_id}/view"))
+            .insert_header((header::USER_AGENT, agent))
+            .insert_header((header::ACCEPT_LANGUAGE, "ko-KR"))
+            .to_request()
+    };
+
+    let resp = test::call_service(&app, view(&agent)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let cookie = resp
+        .response()
+        .cookies()
+        .find(|c| c.name() == "viewed_posts")
+        .expect("조회 기록 쿠키를 내려줘야 합니다")
+        .into_owned();
+    let first: serde_json::Value = test::read_body_json(resp).await;
+    let first_count = first["view_count"].as_i64().unwrap();
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{post_id}/view"))
+        .insert_header((header::USER_AGENT, agent.as_str()))
+        .insert_header((header::ACCEPT_LANGUAGE, "ko-KR"))
+        .cookie(cookie)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT, "쿠키가 있으면 바로 건너뛰어야 합니다");
+
+    let resp = test::call_service(&app, view(&agent)).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT, "쿠키를 지워도 같은 방문자는 다시 세지 않아야 합니다");
+
+    let resp = test::call_service(&app, view(&format!("{agent}-other"))).await;
+    assert_eq!(resp.status(), StatusCode::OK, "다른 방문자는 조회수를 올려야 합니다");
+    let second: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(second["view_count"].as_i64().unwrap(), first_count + 1);
+}
+
+#[actix_web::test]
+async fn test_trending_and_popular_flow() {
+    dotenv().ok();
+
+    let mut config = AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap();
+    config.stats.trending_window_days = 14;
+    config.stats.trending_half_life_hours = 48;
+
+    let pool = db::init_pool(&config.pg);
+    let (old_post, fresh_post) = (8, 9);
+
+    // 다른 테스트의 조회보다 훨씬 크게 넣어 순위를 고정합니다.
+    let today = chrono::Utc::now().date_naive();
+    let client = pool.get().await.unwrap();
+    for (post_id, days_ago, visitors) in [(old_post, 20_u64, 10_000_000), (fresh_post, 0, 5_000_000)] {
+        client
+            .execute(
+                "INSERT INTO post_daily_stats (day, post_id, views, visitors) VALUES ($1, $2, $3, $3)
+                 ON CONFLICT (day, post_id) DO UPDATE SET visitors = EXCLUDED.visitors",
+                &[&(today - chrono::Days::new(days_ago)), &post_id, &visitors]
+            ).await.unwrap();
+    }
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .service(popular_posts)
+        .service(trending_posts);
+
+    let app = test::init_service(app).await;
+    let ids = |posts: Vec<PostSummary>| posts.into_iter().map(|p| p.id).collect::<Vec<_>>();
+
+    let req = test::TestRequest::get().uri("/posts/trending").to_request();
+    let trending = ids(test::call_and_read_body_json(&app, req).await);
+    assert_eq!(trending.first(), Some(&fresh_post), "최근에 많이 읽힌 글이 가장 먼저 와야 합니다");
+    assert!(!trending.contains(&old_post), "기간이 지난 기록은 반영하지 않아야 합니다");
+
+    let req = test::TestRequest::get().uri("/posts/popular?period=week").to_request();
+    let weekly = ids(test::call_and_read_body_json(&app, req).await);
+    assert_eq!(weekly.first(), Some(&fresh_post));
+
+    let req = test::TestRequest::get().uri("/posts/popular?period=month&limit=2").to_request();
+    let monthly = ids(test::call_and_read_body_json(&app, req).await);
+    assert_eq!(monthly, vec![old_post, fresh_post]);
+
+    let req = test::TestRequest::get().uri("/posts/popular?limit=3").to_request();
+    let all_time = ids(test::call_and_read_body_json(&app, req).await);
+    assert_eq!(all_time.len(), 3);
+
+    let req = test::TestRequest::get().uri("/posts/popular?period=year").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_related_posts_flow() {
+    dotenv().ok();
+
+    let config = AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap();
+
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(RelatedPosts::new()))
+        .service(related_posts);
+
+    let app = test::init_service(app).await;
+
+    // 1번 글(rust, actix)과 태그가 겹치는 글은 5, 14(rust)와 6, 13(actix)입니다.
+    let req = test::TestRequest::get().uri("/posts/1/related").to_request();
+    let related: Vec<PostSummary> = test::call_and_read_body_json(&app, req).await;
+    let mut ids: Vec<i32> = related.iter().map(|p| p.id).collect();
+    assert_eq!(ids.len(), 4);
+    ids.sort_unstable();
+    assert_eq!(ids, vec![5, 6, 13, 14]);
+    assert!(related.iter().all(|p| p.tags.iter().any(|t| t == "rust" || t == "actix")));
+
+    let req = test::TestRequest::get().uri("/posts/1/related?limit=2").to_request();
+    let cached: Vec<PostSummary> = test::call_and_read_body_json(&app, req).await;
+    let cached_ids: Vec<i32> = cached.iter().map(|p| p.id).collect();
+    assert_eq!(cached_ids, related.iter().take(2).map(|p| p.id).collect::<Vec<_>>(), "같은 순위를 다시 돌려줘야 합니다");
+
+    let req = test::TestRequest::get().uri("/posts/999999/related").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_post_neighbors_flow() {
+    dotenv().ok();
+
+    let config = AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap();
+
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .service(post_neighbors);
+
+    let app = test::init_service(app).await;
+    let neighbor_ids = |n: &PostNeighbors| (n.previous.as_ref().map(|p| p.id), n.next.as_ref().map(|p| p.id));
+
+    // 샘플 글은 작성 시각이 같으므로 ID 순서를 따릅니다.
+    let req = test::TestRequest::get().uri("/posts/5/neighbors").to_request();
+    let neighbors: PostNeighbors = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(neighbor_ids(&neighbors), (Some(4), Some(6)));
+
+    let req = test::TestRequest::get().uri("/posts/5/neighbors?tag=rust").to_request();
+    let neighbors: PostNeighbors = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(neighbor_ids(&neighbors), (Some(1), Some(14)), "같은 태그의 글 사이에서만 찾아야 합니다");
+
+    let req = test::TestRequest::get().uri("/posts/5/neighbors?tag=Rust").to_request();
+    let neighbors: PostNeighbors = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(neighbor_ids(&neighbors), (Some(1), Some(14)), "태그는 대소문자와 상관없이 걸러야 합니다");
+
+    let req = test::TestRequest::get().uri("/posts/1/neighbors?tag=rust").to_request();
+    let neighbors: PostNeighbors = test::call_and_read_body_json(&app, req).await;
+    assert!(neighbors.previous.is_none(), "첫 글에는 이전 글이 없어야 합니다");
+
+    let req = test::TestRequest::get().uri("/posts/999999/neighbors").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_draft_post_flow() {
+    dotenv().ok();
+
+    let config = AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap();
+    let pool = db::init_pool(&config.pg);
+    let root = std::env::temp_dir().join(format!("blog-draft-test-{}", std::process::id()));
+    let storage: Arc<dyn Storage> = Arc::new(LocalStorage::new(&root, "/media/files"));
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::from(storage))
+        .app_data(web::Data::new(RelatedPosts::new()))
+        .app_data(web::Data::new(VisitorHasher::new()))
+        .app_data(web::Data::new(BotDetector::new(&config.stats)))
+        .app_data(web::Data::new(ViewBuffer::new(&config.stats)))
+        .app_data(web::Data::new(SpamFilter::new(&config.comment)))
+        .service(list_posts)
+        .service(get_post)
+        .service(create_post)
+        .service(update_post)
+        .service(delete_post)
+        .service(view_post)
+        .service(toggle_reaction)
+        .service(record_engagement)
+        .service(create_comment);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::post()
+        .uri("/posts")
+        .cookie(admin_cookie(&config))
+        .set_json(json!({
+            "title": "쓰는 중인 글",
+            "description": "",
+            "body": "<p>초안</p>",
+            "thumbnail": "/placeholder_image.png",
+            "thumbnail_blur": "/placeholder_image.png",
+            "status": "draft",
+        }))
+        .to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(post.status, PostStatus::Draft);
+
+    let listed = |data: &PostListResponse| data.posts.iter().any(|p| p.id == post.id);
+
+    let req = test::TestRequest::get().uri("/posts?pageSize=1000").to_request();
+    let data: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    assert!(!listed(&data), "초안은 공개 목록에 나오면 안 됩니다");
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{}", post.id)).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/posts/{}", post.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK, "관리자는 초안을 볼 수 있어야 합니다");
+
+    // 방문자가 남기는 기록은 발행되지 않은 글에 쌓이면 안 됩니다.
+    let agent = format!("Mozilla/5.0 draft-test/{}", std::process::id());
+    let writes = [
+        test::TestRequest::post()
+            .uri(&format!("/posts/{}/view", post.id))
+            .insert_header((header::USER_AGENT, agent.as_str()))
+            .to_request(),
+        test::TestRequest::post()
+            .uri(&format!("/posts/{}/reactions", post.id))
+            .set_json(json!({ "emoji": "👍" }))
+            .insert_header((header::USER_AGENT, agent.as_str()))
+            .to_request(),
+        test::TestRequest::post()
+            .uri(&format!("/posts/{}/engagement", post.id))
+            .set_payload(r#"{"read_secs":10}"#)
+            .insert_header((header::USER_AGENT, agent.as_str()))
+            .to_request(),
+        test::TestRequest::post()
+            .uri(&format!("/posts/{}/comments", post.id))
+            .set_json(json!({ "nickname": "방문자", "password": "comment-1234", "body": "초안에 댓글" }))
+            .insert_header((header::USER_AGENT, agent.as_str()))
+            .to_request(),
+    ];
+    for req in writes {
+        let uri = req.uri().to_string();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND, "{uri} 는 초안에 기록하지 않아야 합니다");
+    }
+
+    let req = test::TestRequest::get().uri("/posts?status=draft").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let req = test::TestRequest::get()
+        .uri("/posts?status=draft&pageSize=1000")
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let data: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    assert!(listed(&data));
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/posts/{}", post.id))
+        .cookie(admin_cookie(&config))
+        .set_json(json!({ "status": "published" }))
+        .to_request();
+    let updated: Post = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(updated.status, PostStatus::Published);
+
+    let req = test::TestRequest::get().uri("/posts?pageSize=1000").to_request();
+    let data: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    assert!(listed(&data), "발행하면 공개 목록에 나와야 합니다");
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/posts/{}", post.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    test::call_service(&app, req).await;
+
+    std::fs::remove_dir_all(&root).ok();
+}
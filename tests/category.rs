@@ -0,0 +1,138 @@
+use actix_web::{ test, web, App };
+use actix_web::http::StatusCode;
+use blog::blog::dto::{ PostDetail, PostListResponse };
+use blog::blog::handlers::{ get_post, list_posts, update_post };
+use blog::blog::related::RelatedPosts;
+use blog::category::dto::{ CategoryDetail, CategoryNode };
+use blog::category::handlers::{ create_category, delete_category, get_category, list_categories, update_category };
+use blog::category::model::Category;
+use blog::db;
+
+mod common;
+use common::{ admin_cookie, load_config };
+
+fn find(nodes: &[CategoryNode], id: i32) -> Option<&CategoryNode> {
+    nodes.iter().find_map(|node| if node.category.id == id { Some(node) } else { find(&node.children, id) })
+}
+
+#[actix_web::test]
+async fn test_category_flow() {
+    let config = load_config();
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(RelatedPosts::new()))
+        .service(list_categories)
+        .service(get_category)
+        .service(create_category)
+        .service(update_category)
+        .service(delete_category)
+        .service(list_posts)
+        .service(get_post)
+        .service(update_post);
+
+    let app = test::init_service(app).await;
+    let admin = admin_cookie(&config);
+    let run = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+
+    let create = |name: &str, slug: String, parent_id: Option<i32>| {
+        test::TestRequest::post()
+            .uri("/admin/categories")
+            .cookie(admin.clone())
+            .set_json(serde_json::json!({ "name": name, "slug": slug, "parent_id": parent_id }))
+            .to_request()
+    };
+    let root: Category = test::call_and_read_body_json(&app, create("개발", format!("dev-{run}"), None)).await;
+    let child: Category = test::call_and_read_body_json(&app, create("Rust", format!("rust-{run}"), Some(root.id))).await;
+
+    let resp = test::call_service(&app, create("고아", format!("orphan-{run}"), Some(999_999))).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "없는 상위 카테고리는 거절해야 합니다");
+
+    let set_category = |post_id: i32, category_id: Option<i32>| {
+        test::TestRequest::put()
+            .uri(&format!("/posts/{post_id}"))
+            .cookie(admin.clone())
+            .set_json(serde_json::json!({ "category_id": category_id }))
+            .to_request()
+    };
+    let resp = test::call_service(&app, set_category(19, Some(child.id))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = test::call_service(&app, set_category(20, Some(root.id))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = test::call_service(&app, set_category(20, Some(999_999))).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::get().uri("/categories").to_request();
+    let tree: Vec<CategoryNode> = test::call_and_read_body_json(&app, req).await;
+    let root_node = find(&tree, root.id).expect("최상위 카테고리가 트리에 있어야 합니다");
+    assert_eq!((root_node.post_count, root_node.total_count), (1, 2), "하위 카테고리의 글도 합산해야 합니다");
+    assert_eq!(root_node.children.len(), 1);
+    assert_eq!(root_node.children[0].category.id, child.id);
+
+    let req = test::TestRequest::get().uri(&format!("/categories/rust-{run}")).to_request();
+    let detail: CategoryDetail = test::call_and_read_body_json(&app, req).await;
+    let path: Vec<i32> = detail.path.iter().map(|c| c.id).collect();
+    assert_eq!(path, vec![root.id, child.id]);
+
+    let list = |uri: String| test::TestRequest::get().uri(&uri).to_request();
+    let posts: PostListResponse = test::call_and_read_body_json(&app, list(format!("/posts?category=dev-{run}"))).await;
+    let mut ids: Vec<i32> = posts.posts.iter().map(|p| p.id).collect();
+    ids.sort_unstable();
+    assert_eq!((posts.total_count, ids), (2, vec![19, 20]));
+
+    let posts: PostListResponse = test::call_and_read_body_json(&app, list(format!("/posts?category=rust-{run}"))).await;
+    assert_eq!(posts.posts.iter().map(|p| p.id).collect::<Vec<_>>(), vec![19]);
+
+    let resp = test::call_service(&app, list("/posts?category=no-such-category".to_string())).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::get().uri("/posts/19").to_request();
+    let post: PostDetail = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(post.post.category_id, Some(child.id));
+    assert_eq!(post.category_path.iter().map(|c| c.slug.clone()).collect::<Vec<_>>(), vec![format!("dev-{run}"), format!("rust-{run}")]);
+
+    let move_to = |id: i32, parent_id: Option<i32>| {
+        test::TestRequest::put()
+            .uri(&format!("/admin/categories/{id}"))
+            .cookie(admin.clone())
+            .set_json(serde_json::json!({ "parent_id": parent_id }))
+            .to_request()
+    };
+    let resp = test::call_service(&app, move_to(root.id, Some(child.id))).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "하위 카테고리 아래로 옮기면 순환이 생깁니다");
+
+    let req = test::TestRequest::delete().uri(&format!("/admin/categories/{}", root.id)).cookie(admin.clone()).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "하위 카테고리가 있으면 지울 수 없습니다");
+
+    let moved: Category = test::call_and_read_body_json(&app, move_to(child.id, None)).await;
+    assert_eq!(moved.parent_id, None);
+    assert_eq!(moved.name, "Rust");
+
+    let req = test::TestRequest::delete().uri(&format!("/admin/categories/{}", root.id)).cookie(admin.clone()).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::get().uri("/posts/20").to_request();
+    let post: PostDetail = test::call_and_read_body_json(&app, req).await;
+    assert!(post.post.category_id.is_none(), "지운 카테고리의 글은 미분류가 되어야 합니다");
+    assert!(post.category_path.is_empty());
+
+    let resp = test::call_service(&app, set_category(19, None)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let req = test::TestRequest::get().uri("/posts/19").to_request();
+    let post: PostDetail = test::call_and_read_body_json(&app, req).await;
+    assert!(post.post.category_id.is_none(), "null 을 보내면 카테고리를 비워야 합니다");
+
+    let req = test::TestRequest::delete().uri(&format!("/admin/categories/{}", child.id)).cookie(admin.clone()).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = test::call_service(&app, test::TestRequest::post()
+        .uri("/admin/categories")
+        .set_json(serde_json::json!({ "name": "무단", "slug": "unauthorized" }))
+        .to_request()).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,372 @@
+use actix_web::{ test, web, App };
+use actix_web::http::StatusCode;
+use blog::comment::dto::{ CommentListResponse, CommentThread };
+use blog::comment::handlers::{
+    approve_comment,
+    comment_form_token,
+    create_comment,
+    delete_comment,
+    list_comments,
+    moderation_queue,
+    reject_comment,
+    update_comment,
+};
+use blog::comment::model::{ Comment, CommentStatus };
+use blog::comment::spam::{ FormToken, SpamClassifier, SpamFilter, Submission };
+use blog::db;
+use blog::errors::ServiceError;
+use serde_json::json;
+
+mod common;
+use common::{ admin_cookie, load_config };
+
+const POST_ID: i32 = 1;
+
+#[actix_web::test]
+async fn test_comment_thread_flow() {
+    let mut config = load_config();
+    config.comment.min_submit_secs = 0;
+    config.comment.password_attempt_limit = 3;
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(SpamFilter::new(&config.comment)))
+        .service(comment_form_token)
+        .service(list_comments)
+        .service(create_comment)
+        .service(update_comment)
+        .service(delete_comment);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{POST_ID}/comments/token")).to_request();
+    let form: FormToken = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{POST_ID}/comments"))
+        .set_json(
+            json!({ "nickname": "방문자", "password": "comment-1234", "body": "좋은 글 감사합니다", "form_token": form.token })
+        )
+        .to_request();
+    let parent: Comment = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(parent.status, CommentStatus::Approved);
+    assert!(!parent.is_author);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{POST_ID}/comments"))
+        .cookie(admin_cookie(&config))
+        .set_json(json!({ "parent_id": parent.id, "body": "읽어주셔서 감사합니다" }))
+        .to_request();
+    let reply: Comment = test::call_and_read_body_json(&app, req).await;
+    assert!(reply.is_author, "관리자 댓글은 작성자 댓글로 표시되어야 합니다");
+    assert_eq!(reply.nickname, config.admin_user);
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/comments/{}", parent.id))
+        .set_json(json!({ "password": "wrong-0000", "body": "수정" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN, "비밀번호가 틀리면 수정할 수 없어야 합니다");
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/comments/{}", reply.id))
+        .set_json(json!({ "password": "", "body": "가로채기" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED, "작성자 댓글은 관리자만 수정할 수 있어야 합니다");
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/comments/{}", parent.id))
+        .set_json(json!({ "password": "comment-1234", "body": "정말 좋은 글 감사합니다" }))
+        .to_request();
+    let updated: Comment = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(updated.body, "정말 좋은 글 감사합니다");
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/comments/{}", parent.id))
+        .set_json(json!({ "password": "comment-1234" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{POST_ID}/comments")).to_request();
+    let threads: Vec<CommentThread> = test::call_and_read_body_json(&app, req).await;
+    let thread = threads
+        .iter()
+        .find(|t| t.comment.id == parent.id)
+        .expect("답글이 있는 댓글은 삭제 후에도 스레드에 남아야 합니다");
+    assert!(thread.comment.deleted);
+    assert!(thread.comment.body.is_empty());
+    assert_eq!(thread.replies.len(), 1);
+    assert_eq!(thread.replies[0].comment.id, reply.id);
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/comments/{}", reply.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    test::call_service(&app, req).await;
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{POST_ID}/comments")).to_request();
+    let threads: Vec<CommentThread> = test::call_and_read_body_json(&app, req).await;
+    assert!(threads.iter().all(|t| t.comment.id != parent.id), "답글이 없는 삭제 댓글은 숨겨야 합니다");
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{POST_ID}/comments"))
+        .set_json(json!({ "nickname": "방문자", "password": "short", "body": "짧은 비밀번호", "form_token": form.token }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "짧은 비밀번호는 받지 않아야 합니다");
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{POST_ID}/comments"))
+        .set_json(json!({ "nickname": "방문자", "password": "comment-1234", "body": "대입 대상", "form_token": form.token }))
+        .to_request();
+    let target: Comment = test::call_and_read_body_json(&app, req).await;
+
+    let attempt = |password: &str| {
+        test::TestRequest::delete()
+            .uri(&format!("/comments/{}", target.id))
+            .peer_addr("10.1.0.1:40000".parse().unwrap())
+            .set_json(json!({ "password": password }))
+            .to_request()
+    };
+    for i in 0..config.comment.password_attempt_limit {
+        let resp = test::call_service(&app, attempt(&format!("guess-{i:04}"))).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+    let resp = test::call_service(&app, attempt("comment-1234")).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS, "시도 횟수를 넘으면 맞는 비밀번호도 막아야 합니다");
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/comments/{}", target.id))
+        .peer_addr("10.1.0.2:40000".parse().unwrap())
+        .set_json(json!({ "password": "comment-1234" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT, "제한은 IP 마다 따로 세야 합니다");
+}
+
+#[actix_web::test]
+async fn test_comment_moderation_flow() {
+    let mut config = load_config();
+    config.comment.require_approval = true;
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(SpamFilter::new(&config.comment)))
+        .service(list_comments)
+        .service(create_comment)
+        .service(moderation_queue)
+        .service(approve_comment)
+        .service(reject_comment);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{POST_ID}/comments"))
+        .set_json(json!({ "nickname": "검토대상", "password": "pending-abcd", "body": "승인 대기" }))
+        .to_request();
+    let pending: Comment = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(pending.status, CommentStatus::Pending);
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{POST_ID}/comments")).to_request();
+    let threads: Vec<CommentThread> = test::call_and_read_body_json(&app, req).await;
+    assert!(threads.iter().all(|t| t.comment.id != pending.id), "승인 전 댓글은 보이지 않아야 합니다");
+
+    let req = test::TestRequest::get().uri("/admin/comments").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let req = test::TestRequest::get()
+        .uri("/admin/comments?status=pending&pageSize=100")
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let queue: CommentListResponse = test::call_and_read_body_json(&app, req).await;
+    assert!(queue.comments.iter().any(|c| c.comment.id == pending.id));
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/admin/comments/{}/approve", pending.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let approved: Comment = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(approved.status, CommentStatus::Approved);
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{POST_ID}/comments")).to_request();
+    let threads: Vec<CommentThread> = test::call_and_read_body_json(&app, req).await;
+    assert!(threads.iter().any(|t| t.comment.id == pending.id), "승인된 댓글은 보여야 합니다");
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{POST_ID}/comments"))
+        .cookie(admin_cookie(&config))
+        .set_json(json!({ "parent_id": pending.id, "body": "답글" }))
+        .to_request();
+    let reply: Comment = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/admin/comments/{}/reject", pending.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{POST_ID}/comments")).to_request();
+    let threads: Vec<CommentThread> = test::call_and_read_body_json(&app, req).await;
+    assert!(threads.iter().all(|t| t.comment.id != pending.id), "거절된 댓글은 보이지 않아야 합니다");
+    assert!(
+        threads.iter().any(|t| t.comment.id == reply.id),
+        "부모가 거절된 답글은 최상위 스레드로 보여야 합니다"
+    );
+    assert!(threads.windows(2).all(|w| w[0].comment.created_at <= w[1].comment.created_at));
+
+    let req = test::TestRequest::get().uri("/posts/999999/comments").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    pool.get()
+        .await
+        .unwrap()
+        .execute("DELETE FROM comments WHERE id = ANY($1)", &[&vec![reply.id, pending.id]])
+        .await
+        .unwrap();
+}
+
+struct KeywordClassifier;
+
+#[async_trait::async_trait]
+impl SpamClassifier for KeywordClassifier {
+    async fn classify(&self, submission: &Submission<'_>) -> Result<Option<String>, ServiceError> {
+        Ok(submission.body.contains("카지노").then(|| "판별기: 도박".to_string()))
+    }
+}
+
+#[actix_web::test]
+async fn test_comment_spam_flow() {
+    let mut config = load_config();
+    config.comment.require_approval = false;
+    config.comment.min_submit_secs = 0;
+    config.comment.rate_limit_count = 2;
+    config.comment.max_links = 1;
+    config.comment.banned_words = "대출, 광고".to_string();
+    let pool = db::init_pool(&config.pg);
+    let spam = SpamFilter::with_classifier(&config.comment, Box::new(KeywordClassifier));
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(spam))
+        .service(comment_form_token)
+        .service(list_comments)
+        .service(create_comment)
+        .service(update_comment)
+        .service(moderation_queue);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{POST_ID}/comments/token")).to_request();
+    let form: FormToken = test::call_and_read_body_json(&app, req).await;
+
+    let cases = [
+        ("10.0.0.1", json!({ "body": "정상 댓글", "form_token": form.token }), None),
+        ("10.0.0.2", json!({ "body": "봇 댓글", "website": "http://spam.example", "form_token": form.token }), Some("허니팟")),
+        ("10.0.0.3", json!({ "body": "토큰 없는 댓글" }), Some("폼 토큰")),
+        ("10.0.0.4", json!({ "body": "위조 토큰", "form_token": "1.deadbeef" }), Some("폼 토큰")),
+        ("10.0.0.5", json!({ "body": "https://a.example www.b.example", "form_token": form.token }), Some("링크")),
+        ("10.0.0.6", json!({ "body": "저금리 대출 문의", "form_token": form.token }), Some("금칙어")),
+        ("10.0.0.7", json!({ "body": "카지노 바로가기", "form_token": form.token }), Some("판별기")),
+    ];
+
+    let queue = || {
+        test::TestRequest::get()
+            .uri("/admin/comments?status=pending&pageSize=100")
+            .cookie(admin_cookie(&config))
+            .to_request()
+    };
+
+    let mut created = Vec::new();
+    let mut expected = Vec::new();
+    for (ip, mut payload, reason) in cases {
+        payload["nickname"] = json!("손님");
+        payload["password"] = json!("comment-1234");
+        let req = test::TestRequest::post()
+            .uri(&format!("/posts/{POST_ID}/comments"))
+            .peer_addr(format!("{ip}:40000").parse().unwrap())
+            .set_json(payload)
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert!(body.get("spam_reasons").is_none(), "스팸 의심 사유는 작성자에게 돌려주지 않아야 합니다");
+        let comment: Comment = serde_json::from_value(body).unwrap();
+
+        match reason {
+            None => assert_eq!(comment.status, CommentStatus::Approved),
+            Some(_) => {
+                assert_eq!(comment.status, CommentStatus::Pending, "스팸 의심 댓글은 승인 대기여야 합니다 ({ip})");
+            }
+        }
+        created.push(comment.id);
+        expected.push((ip, comment.id, reason));
+    }
+
+    let pending: CommentListResponse = test::call_and_read_body_json(&app, queue()).await;
+    for (ip, id, reason) in &expected {
+        let Some(reason) = reason else {
+            continue;
+        };
+        let queued = pending.comments
+            .iter()
+            .find(|c| c.comment.id == *id)
+            .unwrap_or_else(|| panic!("{ip}: 모더레이션 큐에 있어야 합니다"));
+        assert!(
+            queued.spam_reasons.iter().any(|r| r.contains(reason)),
+            "{ip}: {:?} 에 '{reason}' 사유가 있어야 합니다",
+            queued.spam_reasons
+        );
+    }
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{POST_ID}/comments")).to_request();
+    let threads: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+    assert!(threads.iter().all(|t| t.get("spam_reasons").is_none()), "공개 목록에 스팸 의심 사유가 없어야 합니다");
+
+    // 승인된 댓글도 수정하면서 금칙어를 넣으면 다시 승인 대기로 돌아가야 합니다.
+    let (_, clean_id, _) = expected[0];
+    let req = test::TestRequest::put()
+        .uri(&format!("/comments/{clean_id}"))
+        .peer_addr("10.0.0.1:40000".parse().unwrap())
+        .set_json(json!({ "password": "comment-1234", "body": "광고 보고 가세요" }))
+        .to_request();
+    let edited: Comment = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(edited.status, CommentStatus::Pending, "수정한 본문도 스팸 규칙을 다시 거쳐야 합니다");
+
+    let pending: CommentListResponse = test::call_and_read_body_json(&app, queue()).await;
+    let queued = pending.comments.iter().find(|c| c.comment.id == clean_id).expect("수정된 댓글이 큐에 있어야 합니다");
+    assert!(queued.spam_reasons.iter().any(|r| r.contains("금칙어")));
+
+    let post_from = |ip: &str| {
+        test::TestRequest::post()
+            .uri(&format!("/posts/{POST_ID}/comments"))
+            .peer_addr(format!("{ip}:40000").parse().unwrap())
+            .set_json(json!({ "nickname": "손님", "password": "comment-1234", "body": "도배", "form_token": form.token }))
+            .to_request()
+    };
+    let resp = test::call_service(&app, post_from("10.0.0.1")).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    created.push(test::read_body_json::<Comment, _>(resp).await.id);
+
+    let resp = test::call_service(&app, post_from("10.0.0.1")).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS, "IP 당 제한을 넘으면 거절해야 합니다");
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{POST_ID}/comments"))
+        .peer_addr("10.0.0.1:40000".parse().unwrap())
+        .cookie(admin_cookie(&config))
+        .set_json(json!({ "body": "관리자는 제한 없음" }))
+        .to_request();
+    let admin_comment: Comment = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(admin_comment.status, CommentStatus::Approved);
+    created.push(admin_comment.id);
+
+    pool.get().await.unwrap().execute("DELETE FROM comments WHERE id = ANY($1)", &[&created]).await.unwrap();
+}
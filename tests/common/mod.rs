@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+
+use actix_web::cookie::Cookie;
+use blog::config::AppConfig;
+use blog::user::handlers::AUTH_COOKIE;
+use blog::user::model::Claims;
+use confik::{ Configuration, EnvSource };
+use dotenvy::dotenv;
+use jsonwebtoken::{ encode, EncodingKey, Header };
+
+pub fn load_config() -> AppConfig {
+    dotenv().ok();
+    AppConfig::builder().override_with(EnvSource::new()).try_build().unwrap()
+}
+
+pub fn admin_cookie(config: &AppConfig) -> Cookie<'static> {
+    let claims = Claims { sub: config.admin_user.clone(), role: "Admin".to_string(), exp: usize::MAX / 2 };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes())
+    ).unwrap();
+    Cookie::new(AUTH_COOKIE, token)
+}
+
+pub const BOUNDARY: &str = "----blog-test-boundary";
+
+pub fn multipart_body(filename: &str, bytes: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+    ).into_bytes();
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
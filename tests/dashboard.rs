@@ -0,0 +1,48 @@
+use actix_web::{ test, web, App };
+use actix_web::http::StatusCode;
+use blog::dashboard::dto::DashboardSummary;
+use blog::dashboard::handlers::dashboard;
+use blog::db;
+
+mod common;
+use common::{ admin_cookie, load_config };
+
+#[actix_web::test]
+async fn test_dashboard_flow() {
+    let config = load_config();
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .service(dashboard);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::get().uri("/admin/dashboard").cookie(admin_cookie(&config)).to_request();
+    let summary: DashboardSummary = test::call_and_read_body_json(&app, req).await;
+
+    assert!(summary.posts.total >= 20);
+    assert_eq!(summary.posts.published + summary.posts.draft, summary.posts.total);
+    assert!(summary.posts.last_7_days <= summary.posts.last_30_days);
+    assert!(summary.posts.last_30_days <= summary.posts.total);
+
+    let days: Vec<i32> = summary.traffic.iter().map(|t| t.days).collect();
+    assert_eq!(days, vec![7, 30]);
+    assert!(summary.traffic[0].views <= summary.traffic[1].views);
+
+    assert!(summary.top_posts.len() <= 5);
+    assert!(summary.pending_comments.comments.len() <= 10);
+    assert_eq!(summary.pending_comments.total_count, summary.comments.pending);
+
+    let rust = summary.tags.iter().find(|t| t.tag == "rust").expect("태그 사용 현황에 rust 가 있어야 합니다");
+    assert!(rust.posts >= 2);
+    assert!(summary.tags.windows(2).all(|w| w[0].posts >= w[1].posts), "많이 쓰인 태그가 먼저 와야 합니다");
+
+    assert!(summary.storage.unused_files <= summary.storage.files);
+    assert!(summary.storage.unused_bytes <= summary.storage.bytes);
+
+    let req = test::TestRequest::get().uri("/admin/dashboard").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,418 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use actix_web::{ test, web, App };
+use actix_web::http::StatusCode;
+use blog::blog::handlers::{ create_post, delete_post, refresh_thumbnail_variants };
+use blog::blog::related::RelatedPosts;
+use blog::blog::model::Post;
+use blog::db;
+use blog::media::cache::DiskCache;
+use blog::media::dto::{ MediaListResponse, SignImageResponse };
+use blog::media::handlers::{ delete_media, list_media, resize_image, serve_media, sign_image, upload_media };
+use blog::media::model::Media;
+use blog::media::storage::{ LocalStorage, Storage };
+use image::{ ImageFormat, Rgb, RgbImage };
+use serde_json::json;
+
+mod common;
+use common::{ admin_cookie, load_config, multipart_body, BOUNDARY };
+
+/// Orientation=6(시계 방향 90도 회전)과 GPS 태그가 담긴 EXIF APP1 세그먼트를 SOI 바로 뒤에 끼워 넣습니다.
+fn with_exif_orientation(jpeg: &[u8]) -> Vec<u8> {
+    let mut tiff = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00, 0x02, 0x00];
+    tiff.extend_from_slice(&[0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00]);
+    tiff.extend_from_slice(&[0x25, 0x88, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x26, 0x00, 0x00, 0x00]);
+    tiff.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+    tiff.extend_from_slice(&[0x00, 0x00]);
+
+    let mut payload = b"Exif\0\0".to_vec();
+    payload.extend_from_slice(&tiff);
+
+    let mut out = jpeg[..2].to_vec();
+    out.extend_from_slice(&[0xff, 0xe1]);
+    out.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+    out.extend_from_slice(&payload);
+    out.extend_from_slice(&jpeg[2..]);
+    out
+}
+
+/// 단순 WebP 를 VP8X 확장 형식으로 감싸고 EXIF 청크를 붙입니다.
+fn with_webp_exif(simple: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let exif = b"MM\0*\0\0\0\x08\0\0";
+    let mut body = b"WEBP".to_vec();
+    body.extend_from_slice(b"VP8X");
+    body.extend_from_slice(&10u32.to_le_bytes());
+    body.extend_from_slice(&[0x08, 0, 0, 0]);
+    body.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+    body.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+    body.extend_from_slice(&simple[12..]);
+    body.extend_from_slice(b"EXIF");
+    body.extend_from_slice(&(exif.len() as u32).to_le_bytes());
+    body.extend_from_slice(exif);
+
+    let mut webp = b"RIFF".to_vec();
+    webp.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    webp.extend_from_slice(&body);
+    webp
+}
+
+#[actix_web::test]
+async fn test_upload_media_success_flow() {
+    let config = load_config();
+
+    let root = std::env::temp_dir().join(format!("blog-media-test-{}", std::process::id()));
+    let storage: Arc<dyn Storage> = Arc::new(LocalStorage::new(&root, "/media/files"));
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::from(storage))
+        .service(upload_media)
+        .service(serve_media);
+
+    let app = test::init_service(app).await;
+
+    let mut png = Vec::new();
+    RgbImage::from_pixel(32, 24, Rgb([200, 80, 40]))
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/media")
+        .insert_header(("content-type", format!("multipart/form-data; boundary={BOUNDARY}")))
+        .set_payload(multipart_body("../../tile.png", &png))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED, "관리자 쿠키 없이는 업로드할 수 없어야 합니다");
+
+    let req = test::TestRequest::post()
+        .uri("/media")
+        .cookie(admin_cookie(&config))
+        .insert_header(("content-type", format!("multipart/form-data; boundary={BOUNDARY}")))
+        .set_payload(multipart_body("../../tile.png", &png))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED, "응답 상태가 201이어야 합니다");
+
+    let media: Media = test::read_body_json(resp).await;
+    assert!(media.key.ends_with(".png"), "키는 업로드 파일 이름이 아닌 내용 해시로 만들어져야 합니다");
+    assert_eq!(media.url, format!("/media/files/{}", media.key));
+    assert_eq!(media.filename, "../../tile.png");
+    assert_eq!((media.width, media.height), (32, 24));
+    assert!(media.placeholder.starts_with("data:image/jpeg;base64,"));
+    assert!(
+        media.variants.0.iter().any(|v| v.content_type == "image/webp" && v.width == 32),
+        "원본보다 작은 폭이 없으면 원본 폭의 WebP 변환본이 있어야 합니다"
+    );
+    let variant_prefix = format!("/media/files/{}/", media.key.trim_end_matches(".png"));
+    assert!(media.variants.0.iter().all(|v| v.url.starts_with(&variant_prefix)));
+
+    let req = test::TestRequest::get().uri(&media.url).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let stored = image::load_from_memory(&test::read_body(resp).await).unwrap();
+    assert_eq!((stored.width(), stored.height()), (32, 24));
+
+    let webp = media.variants.0.iter().find(|v| v.content_type == "image/webp").unwrap();
+    let req = test::TestRequest::get().uri(&webp.url).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = test::read_body(resp).await;
+    assert_eq!(&body[12..16], b"VP8 ", "WebP 변환본은 손실 압축이어야 합니다");
+
+    let req = test::TestRequest::post()
+        .uri("/media")
+        .cookie(admin_cookie(&config))
+        .insert_header(("content-type", format!("multipart/form-data; boundary={BOUNDARY}")))
+        .set_payload(multipart_body("notes.txt", b"not an image"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "이미지가 아닌 파일은 거부해야 합니다");
+
+    let mut jpeg = Vec::new();
+    RgbImage::from_pixel(40, 20, Rgb([10, 200, 90]))
+        .write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg)
+        .unwrap();
+    let req = test::TestRequest::post()
+        .uri("/media")
+        .cookie(admin_cookie(&config))
+        .insert_header(("content-type", format!("multipart/form-data; boundary={BOUNDARY}")))
+        .set_payload(multipart_body("phone.jpg", &with_exif_orientation(&jpeg)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let media: Media = test::read_body_json(resp).await;
+    assert_eq!((media.width, media.height), (20, 40), "EXIF Orientation 에 맞게 회전되어야 합니다");
+
+    let req = test::TestRequest::get().uri(&media.url).to_request();
+    let stored = test::read_body(test::call_service(&app, req).await).await;
+    assert!(!stored.windows(4).any(|w| w == b"Exif"), "저장된 원본에는 EXIF 가 없어야 합니다");
+    let stored = image::load_from_memory(&stored).unwrap();
+    assert_eq!((stored.width(), stored.height()), (20, 40));
+
+    let mut simple = Vec::new();
+    RgbImage::from_pixel(24, 12, Rgb([90, 30, 160]))
+        .write_to(&mut Cursor::new(&mut simple), ImageFormat::WebP)
+        .unwrap();
+    let webp = with_webp_exif(&simple, 24, 12);
+    let req = test::TestRequest::post()
+        .uri("/media")
+        .cookie(admin_cookie(&config))
+        .insert_header(("content-type", format!("multipart/form-data; boundary={BOUNDARY}")))
+        .set_payload(multipart_body("photo.webp", &webp))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let media: Media = test::read_body_json(resp).await;
+    let req = test::TestRequest::get().uri(&media.url).to_request();
+    let stored = test::read_body(test::call_service(&app, req).await).await;
+    assert!(!stored.windows(4).any(|w| w == b"EXIF"), "WebP 원본에서도 EXIF 청크를 지워야 합니다");
+    assert!(
+        stored.windows(simple.len() - 12).any(|w| w == &simple[12..]),
+        "WebP 원본은 다시 인코딩하지 않고 이미지 데이터를 그대로 두어야 합니다"
+    );
+    let stored = image::load_from_memory(&stored).unwrap();
+    assert_eq!((stored.width(), stored.height()), (24, 12));
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[actix_web::test]
+async fn test_resize_image_signed_flow() {
+    let config = load_config();
+
+    let root = std::env::temp_dir().join(format!("blog-resize-test-{}", std::process::id()));
+    let storage: Arc<dyn Storage> = Arc::new(LocalStorage::new(root.join("files"), "/media/files"));
+    let cache = DiskCache::open(root.join("cache"), 1024 * 1024).unwrap();
+
+    let mut png = Vec::new();
+    RgbImage::from_pixel(64, 48, Rgb([20, 120, 220]))
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    storage.put("source.png", png, "image/png").await.unwrap();
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::from(storage))
+        .app_data(web::Data::new(cache))
+        .service(sign_image)
+        .service(resize_image);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::post()
+        .uri("/img/sign")
+        .cookie(admin_cookie(&config))
+        .set_json(json!({ "key": "source.png", "w": 32, "h": 32, "fit": "cover", "fmt": "png" }))
+        .to_request();
+    let signed: SignImageResponse = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::get().uri(&signed.url).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "image/png");
+    let etag = resp.headers().get("etag").expect("ETag 헤더가 있어야 합니다").clone();
+    assert!(resp.headers().get("cache-control").unwrap().to_str().unwrap().contains("immutable"));
+
+    let resized = image::load_from_memory(&test::read_body(resp).await).unwrap();
+    assert_eq!((resized.width(), resized.height()), (32, 32), "cover 는 요청 크기로 잘라야 합니다");
+
+    let req = test::TestRequest::get()
+        .uri(&signed.url)
+        .insert_header(("if-none-match", etag.clone()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+    // 원본과 캐시가 없어도 ETag 가 맞으면 파일을 읽지 않고 304 를 돌려줘야 합니다.
+    std::fs::remove_file(root.join("files/source.png")).unwrap();
+    std::fs::remove_dir_all(root.join("cache")).unwrap();
+    let req = test::TestRequest::get()
+        .uri(&signed.url)
+        .insert_header(("if-none-match", etag))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+    let tampered = signed.url.replace("w=32", "w=2000");
+    let req = test::TestRequest::get().uri(&tampered).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED, "서명과 다른 파라미터는 거부해야 합니다");
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[actix_web::test]
+async fn test_media_usage_tracking_flow() {
+    let config = load_config();
+
+    let root = std::env::temp_dir().join(format!("blog-usage-test-{}", std::process::id()));
+    let storage: Arc<dyn Storage> = Arc::new(LocalStorage::new(&root, "/media/files"));
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::from(storage))
+        .app_data(web::Data::new(RelatedPosts::new()))
+        .service(upload_media)
+        .service(list_media)
+        .service(delete_media)
+        .service(create_post)
+        .service(delete_post);
+
+    let app = test::init_service(app).await;
+
+    let mut png = Vec::new();
+    RgbImage::from_pixel(16, 16, Rgb([150, 7, 77]))
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    let req = test::TestRequest::post()
+        .uri("/media")
+        .cookie(admin_cookie(&config))
+        .insert_header(("content-type", format!("multipart/form-data; boundary={BOUNDARY}")))
+        .set_payload(multipart_body("usage.png", &png))
+        .to_request();
+    let media: Media = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::post()
+        .uri("/posts")
+        .cookie(admin_cookie(&config))
+        .set_json(json!({
+            "title": "미디어 사용 기록",
+            "description": "",
+            "body": format!("<p><img src=\"{}\"></p>", media.url),
+            "thumbnail": "/placeholder_image.png",
+            "thumbnail_blur": "/placeholder_image.png",
+        }))
+        .to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/media?q={}", media.key))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let list: MediaListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(list.total_count, 1);
+    assert_eq!(list.media[0].usage_count, 1, "본문에서 참조한 미디어는 사용 중으로 기록되어야 합니다");
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/media/{}", media.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "사용 중인 미디어는 force 없이 지울 수 없어야 합니다");
+    assert!(root.join(&media.key).exists(), "지우지 못했으면 파일도 남아 있어야 합니다");
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/posts/{}", post.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    test::call_service(&app, req).await;
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/media/{}", media.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert!(!root.join(&media.key).exists(), "원본 파일도 지워져야 합니다");
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/media/{}", media.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[actix_web::test]
+async fn test_thumbnail_import_flow() {
+    let config = load_config();
+
+    let root = std::env::temp_dir().join(format!("blog-thumbnail-test-{}", std::process::id()));
+    let storage: Arc<dyn Storage> = Arc::new(LocalStorage::new(&root, "/media/files"));
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::from(storage.clone()))
+        .app_data(web::Data::new(RelatedPosts::new()))
+        .service(list_media)
+        .service(delete_media)
+        .service(create_post)
+        .service(refresh_thumbnail_variants)
+        .service(delete_post);
+
+    let app = test::init_service(app).await;
+
+    // 라이브러리를 거치지 않고 저장소에 바로 올라간 이미지입니다.
+    let pid = std::process::id();
+    let mut png = Vec::new();
+    RgbImage::from_pixel(40, 20, Rgb([(pid % 251) as u8, 99, 3]))
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    let loose_key = format!("loose-{pid}.png");
+    storage.put(&loose_key, png, "image/png").await.unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/posts")
+        .cookie(admin_cookie(&config))
+        .set_json(json!({
+            "title": "외부 썸네일",
+            "description": "",
+            "body": "<p>본문</p>",
+            "thumbnail": format!("/media/files/{loose_key}"),
+            "thumbnail_blur": "/placeholder_image.png",
+        }))
+        .to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{}/thumbnail/variants", post.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    assert!(!post.thumbnail_variants.0.is_empty());
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/media?q=loose-{pid}"))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let list: MediaListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(list.total_count, 1, "썸네일 원본이 미디어로 등록되어야 합니다");
+    let media = &list.media[0];
+    assert_eq!(media.usage_count, 1, "썸네일로 쓰는 미디어는 사용 중으로 기록되어야 합니다");
+    for variant in &post.thumbnail_variants.0 {
+        assert!(variant.url.contains(&media.hash), "변환본은 등록된 미디어의 것이어야 합니다");
+        let key = variant.url.trim_start_matches("/media/files/");
+        assert!(root.join(key).exists());
+    }
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/posts/{}", post.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    test::call_service(&app, req).await;
+
+    // 글이 사라지면 변환본도 고아 미디어로 함께 지울 수 있어야 합니다.
+    let req = test::TestRequest::delete()
+        .uri(&format!("/media/{}", media.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    for variant in &post.thumbnail_variants.0 {
+        let key = variant.url.trim_start_matches("/media/files/");
+        assert!(!root.join(key).exists(), "변환본 파일도 지워져야 합니다");
+    }
+
+    std::fs::remove_dir_all(&root).ok();
+}
@@ -0,0 +1,110 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use actix_web::{ test, web, App };
+use actix_web::http::StatusCode;
+use blog::db;
+use blog::errors::ServiceError;
+use blog::media::dto::OrphanReport;
+use blog::media::handlers::{ cleanup_orphans, delete_media, serve_media, upload_media };
+use blog::media::model::Media;
+use blog::media::storage::{ S3Storage, Storage };
+use image::{ ImageFormat, Rgb, RgbImage };
+use serde_json::json;
+
+mod common;
+use common::{ admin_cookie, load_config, multipart_body, BOUNDARY };
+
+/// 실행할 때마다 내용 해시가 달라지도록 색을 바꿉니다.
+fn unique_png(seed: u32) -> Vec<u8> {
+    let [r, g, b, _] = (std::process::id() ^ seed).to_le_bytes();
+    let mut png = Vec::new();
+    RgbImage::from_pixel(48, 32, Rgb([r, g, b]))
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    png
+}
+
+async fn assert_removed(storage: &dyn Storage, media: &Media) {
+    let mut keys = vec![media.key.clone()];
+    keys.extend(media.variants.0.iter().filter_map(|v| storage.key_from_url(&v.url)));
+    for key in keys {
+        assert!(
+            matches!(storage.get(&key).await, Err(ServiceError::NotFound)),
+            "S3 에서 {key} 가 지워져야 합니다"
+        );
+    }
+}
+
+/// `docker compose up minio` 로 띄운 MinIO 같은 S3 호환 스토리지가 있을 때만 돕니다.
+#[actix_web::test]
+async fn test_s3_storage_flow() {
+    let config = load_config();
+    let s3 = &config.storage;
+    if s3.s3_endpoint.is_none() || s3.s3_bucket.is_none() || s3.s3_access_key.is_none() || s3.s3_secret_key.is_none() {
+        eprintln!("STORAGE__S3_* 가 없어 S3 스토리지 테스트를 건너뜁니다");
+        return;
+    }
+
+    let storage: Arc<dyn Storage> = Arc::new(S3Storage::new(s3).unwrap());
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::from(storage.clone()))
+        .service(upload_media)
+        .service(serve_media)
+        .service(delete_media)
+        .service(cleanup_orphans);
+
+    let app = test::init_service(app).await;
+
+    let upload = |png: Vec<u8>| {
+        test::TestRequest::post()
+            .uri("/media")
+            .cookie(admin_cookie(&config))
+            .insert_header(("content-type", format!("multipart/form-data; boundary={BOUNDARY}")))
+            .set_payload(multipart_body("s3.png", &png))
+            .to_request()
+    };
+
+    let resp = test::call_service(&app, upload(unique_png(1))).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let media: Media = test::read_body_json(resp).await;
+    assert!(!media.variants.0.is_empty());
+
+    let req = test::TestRequest::get().uri(&format!("/media/files/{}", media.key)).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let stored = image::load_from_memory(&test::read_body(resp).await).unwrap();
+    assert_eq!((stored.width(), stored.height()), (48, 32));
+
+    for variant in &media.variants.0 {
+        let key = storage.key_from_url(&variant.url).expect("변환본 URL 은 스토리지 키로 돌아가야 합니다");
+        assert!(!storage.get(&key).await.unwrap().is_empty());
+    }
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/media/{}", media.id))
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_removed(storage.as_ref(), &media).await;
+
+    // 어떤 글에서도 쓰지 않는 파일은 고아 정리로 지워져야 합니다.
+    let resp = test::call_service(&app, upload(unique_png(2))).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let orphan: Media = test::read_body_json(resp).await;
+
+    let req = test::TestRequest::post()
+        .uri("/media/orphans/cleanup")
+        .cookie(admin_cookie(&config))
+        .set_json(json!({ "dry_run": false, "min_age_hours": 0 }))
+        .to_request();
+    let report: OrphanReport = test::call_and_read_body_json(&app, req).await;
+    assert!(report.orphans.iter().any(|m| m.id == orphan.id));
+    assert!(report.deleted >= 1);
+    assert_removed(storage.as_ref(), &orphan).await;
+}
@@ -0,0 +1,141 @@
+use actix_web::{ test, web, App };
+use actix_web::http::StatusCode;
+use blog::blog::dto::{ PostDetail, PostNeighbors };
+use blog::blog::handlers::{ get_post, post_neighbors };
+use blog::db;
+use blog::series::dto::{ SeriesDetail, SeriesSummary };
+use blog::series::handlers::{ create_series, delete_series, get_series, list_series, set_series_posts, update_series };
+use blog::series::model::Series;
+
+mod common;
+use common::{ admin_cookie, load_config };
+
+#[actix_web::test]
+async fn test_series_flow() {
+    let config = load_config();
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .service(list_series)
+        .service(get_series)
+        .service(create_series)
+        .service(update_series)
+        .service(delete_series)
+        .service(set_series_posts)
+        .service(get_post)
+        .service(post_neighbors);
+
+    let app = test::init_service(app).await;
+    let admin = admin_cookie(&config);
+    let slug = format!("actix-tutorial-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+
+    let req = test::TestRequest::post()
+        .uri("/admin/series")
+        .cookie(admin.clone())
+        .set_json(serde_json::json!({ "title": "Actix 튜토리얼", "slug": "Actix 튜토리얼" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "URL 에 쓸 수 없는 슬러그는 거절해야 합니다");
+
+    let req = test::TestRequest::post()
+        .uri("/admin/series")
+        .cookie(admin.clone())
+        .set_json(serde_json::json!({ "title": "Actix 튜토리얼", "slug": slug, "description": "세 편짜리 입문" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let series: Series = test::read_body_json(resp).await;
+
+    let req = test::TestRequest::post()
+        .uri("/admin/series")
+        .cookie(admin.clone())
+        .set_json(serde_json::json!({ "title": "중복", "slug": slug }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "같은 슬러그는 두 번 쓸 수 없습니다");
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/admin/series/{}/posts", series.id))
+        .cookie(admin.clone())
+        .set_json(serde_json::json!({ "post_ids": [17, 15, 16] }))
+        .to_request();
+    let detail: SeriesDetail = test::call_and_read_body_json(&app, req).await;
+    let ids: Vec<i32> = detail.posts.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![17, 15, 16], "보낸 순서대로 편이 정해져야 합니다");
+
+    let req = test::TestRequest::get().uri("/posts/15").to_request();
+    let post: PostDetail = test::call_and_read_body_json(&app, req).await;
+    let nav = post.series.expect("시리즈 정보가 함께 와야 합니다");
+    assert_eq!((nav.part, nav.total), (2, 3));
+    assert_eq!(nav.slug, slug);
+    assert_eq!(nav.previous.map(|p| p.post_id), Some(17));
+    assert_eq!(nav.next.map(|p| p.post_id), Some(16));
+
+    let req = test::TestRequest::get().uri("/posts/17/neighbors?series=true").to_request();
+    let neighbors: PostNeighbors = test::call_and_read_body_json(&app, req).await;
+    assert!(neighbors.previous.is_none());
+    assert_eq!(neighbors.next.map(|p| p.id), Some(15));
+
+    let req = test::TestRequest::post()
+        .uri("/admin/series")
+        .cookie(admin.clone())
+        .set_json(serde_json::json!({ "title": "다른 시리즈", "slug": format!("{slug}-other") }))
+        .to_request();
+    let other: Series = test::call_and_read_body_json(&app, req).await;
+    let req = test::TestRequest::put()
+        .uri(&format!("/admin/series/{}/posts", other.id))
+        .cookie(admin.clone())
+        .set_json(serde_json::json!({ "post_ids": [18, 16] }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "다른 시리즈에 속한 글은 넣을 수 없습니다");
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/admin/series/{}/posts", other.id))
+        .cookie(admin.clone())
+        .set_json(serde_json::json!({ "post_ids": [18, 18] }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/admin/series/{}", series.id))
+        .cookie(admin.clone())
+        .set_json(serde_json::json!({ "title": "Actix 입문" }))
+        .to_request();
+    let updated: Series = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(updated.title, "Actix 입문");
+    assert_eq!(updated.slug, slug);
+
+    let req = test::TestRequest::get().uri("/series").to_request();
+    let list: Vec<SeriesSummary> = test::call_and_read_body_json(&app, req).await;
+    let summary = list.iter().find(|s| s.series.id == series.id).unwrap();
+    assert_eq!(summary.post_count, 3);
+
+    let req = test::TestRequest::get().uri(&format!("/series/{slug}")).to_request();
+    let detail: SeriesDetail = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(detail.series.title, "Actix 입문");
+
+    for id in [series.id, other.id] {
+        let req = test::TestRequest::delete().uri(&format!("/admin/series/{id}")).cookie(admin.clone()).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    }
+
+    let req = test::TestRequest::get().uri("/posts/15").to_request();
+    let post: PostDetail = test::call_and_read_body_json(&app, req).await;
+    assert!(post.series.is_none(), "시리즈를 지우면 글에서도 빠져야 합니다");
+
+    let req = test::TestRequest::get().uri(&format!("/series/{slug}")).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::post()
+        .uri("/admin/series")
+        .set_json(serde_json::json!({ "title": "무단", "slug": "unauthorized" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,482 @@
+use actix_web::{ test, web, App };
+use actix_web::http::{ header, StatusCode };
+use blog::blog::handlers::{ get_post, record_engagement, view_post };
+use blog::blog::model::Post;
+use blog::db;
+use blog::stats::bot::BotDetector;
+use blog::stats::dto::{ BotTrafficReport, CampaignReport, EngagementReport, ReferrerReport, TimeSeries };
+use blog::stats::export::PostExport;
+use blog::stats::handlers::{
+    bot_traffic,
+    campaign_report,
+    engagement_report,
+    export_daily,
+    export_posts,
+    post_stats,
+    referrer_report,
+    site_stats,
+};
+use blog::stats::views::ViewBuffer;
+use blog::visitor::VisitorHasher;
+use chrono::{ NaiveDate, Utc };
+
+mod common;
+use common::{ admin_cookie, load_config };
+
+#[actix_web::test]
+async fn test_bot_views_flow() {
+    let mut config = load_config();
+    config.stats.bot_views_per_minute = 2;
+    config.stats.bot_agents = "blog-monitor".to_string();
+    let pool = db::init_pool(&config.pg);
+    let post_id = 4;
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(VisitorHasher::new()))
+        .app_data(web::Data::new(BotDetector::new(&config.stats)))
+        .app_data(web::Data::new(ViewBuffer::new(&config.stats)))
+        .service(get_post)
+        .service(view_post)
+        .service(bot_traffic);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::get().uri("/admin/stats/bots").cookie(admin_cookie(&config)).to_request();
+    let before: BotTrafficReport = test::call_and_read_body_json(&app, req).await;
+    let hits_for = |report: &BotTrafficReport, reason: &str| {
+        report.by_reason
+            .iter()
+            .find(|r| r.reason == reason)
+            .map(|r| r.hits)
+            .unwrap_or(0)
+    };
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{post_id}")).to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    let view_count = post.view_count;
+
+    let agents = [
+        "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        "facebookexternalhit/1.1",
+        "curl/8.5.0",
+        "Blog-Monitor/1.0",
+        "",
+    ];
+    for agent in agents {
+        let req = test::TestRequest::post()
+            .uri(&format!("/posts/{post_id}/view"))
+            .insert_header((header::USER_AGENT, agent))
+            .insert_header((header::ACCEPT_LANGUAGE, "en-US"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT, "봇 조회는 세지 않아야 합니다: {agent:?}");
+    }
+
+    let browser = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0 Safari/537.36";
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{post_id}/view"))
+        .insert_header((header::USER_AGENT, browser))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT, "Accept-Language 가 없으면 봇으로 봐야 합니다");
+
+    for i in 0..3 {
+        let req = test::TestRequest::post()
+            .uri(&format!("/posts/{post_id}/view"))
+            .peer_addr("192.0.2.10:5000".parse().unwrap())
+            .insert_header((header::USER_AGENT, format!("{browser} run/{i}")))
+            .insert_header((header::ACCEPT_LANGUAGE, "ko-KR"))
+            .to_request();
+        test::call_service(&app, req).await;
+    }
+
+    let req = test::TestRequest::get().uri("/admin/stats/bots?days=1").cookie(admin_cookie(&config)).to_request();
+    let after: BotTrafficReport = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(after.days, 1);
+    for reason in ["ua:googlebot", "ua:facebookexternalhit", "ua:curl/", "ua:blog-monitor", "no-user-agent", "no-accept-language", "rate"] {
+        assert!(hits_for(&after, reason) > hits_for(&before, reason), "'{reason}' 봇 조회가 따로 기록되어야 합니다");
+    }
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{post_id}")).to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    assert!(post.view_count <= view_count + 2, "분당 제한을 넘은 조회는 세지 않아야 합니다");
+
+    let req = test::TestRequest::get().uri("/admin/stats/bots").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_buffered_views_flow() {
+    let mut config = load_config();
+    config.stats.buffer_views = true;
+    let pool = db::init_pool(&config.pg);
+    let post_id = 5;
+    let views = web::Data::new(ViewBuffer::new(&config.stats));
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(VisitorHasher::new()))
+        .app_data(web::Data::new(BotDetector::new(&config.stats)))
+        .app_data(views.clone())
+        .service(get_post)
+        .service(view_post);
+
+    let app = test::init_service(app).await;
+
+    // 다른 테스트가 남긴 증가분이 섞이지 않도록 먼저 비웁니다.
+    views.flush(&pool).await.unwrap();
+    let req = test::TestRequest::get().uri(&format!("/posts/{post_id}")).to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    let base = post.view_count;
+
+    let run = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    for i in 1..=3 {
+        let req = test::TestRequest::post()
+            .uri(&format!("/posts/{post_id}/view"))
+            .insert_header((header::USER_AGENT, format!("Mozilla/5.0 buffered-test/{run}/{i}")))
+            .insert_header((header::ACCEPT_LANGUAGE, "ko-KR"))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["view_count"].as_i64().unwrap(), i64::from(base + i), "근사 조회수를 바로 돌려줘야 합니다");
+    }
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{post_id}")).to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(post.view_count, base, "반영 전에는 DB 조회수가 그대로여야 합니다");
+
+    assert_eq!(views.flush(&pool).await.unwrap(), 1, "글 하나에 대한 증가분을 한 번에 반영해야 합니다");
+
+    let req = test::TestRequest::get().uri(&format!("/posts/{post_id}")).to_request();
+    let post: Post = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(post.view_count, base + 3);
+
+    let req = test::TestRequest::post()
+        .uri("/posts/999999/view")
+        .insert_header((header::USER_AGENT, format!("Mozilla/5.0 buffered-test/{run}/missing")))
+        .insert_header((header::ACCEPT_LANGUAGE, "ko-KR"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_daily_stats_flow() {
+    let mut config = load_config();
+    config.stats.buffer_views = false;
+    let pool = db::init_pool(&config.pg);
+    let post_id = 6;
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(VisitorHasher::new()))
+        .app_data(web::Data::new(BotDetector::new(&config.stats)))
+        .app_data(web::Data::new(ViewBuffer::new(&config.stats)))
+        .service(view_post)
+        .service(site_stats)
+        .service(post_stats);
+
+    let app = test::init_service(app).await;
+    let today = Utc::now().date_naive();
+
+    let series = |uri: String| {
+        test::TestRequest::get().uri(&uri).cookie(admin_cookie(&config)).to_request()
+    };
+    let req = series(format!("/admin/stats/posts/{post_id}?from={today}&to={today}"));
+    let before: TimeSeries = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(before.points.len(), 1);
+
+    let agent = format!("Mozilla/5.0 daily-stats-test/{}", Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    for _ in 0..2 {
+        let req = test::TestRequest::post()
+            .uri(&format!("/posts/{post_id}/view"))
+            .insert_header((header::USER_AGENT, agent.as_str()))
+            .insert_header((header::ACCEPT_LANGUAGE, "ko-KR"))
+            .to_request();
+        test::call_service(&app, req).await;
+    }
+
+    let req = series(format!("/admin/stats/posts/{post_id}?from={today}&to={today}"));
+    let after: TimeSeries = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(after.points[0].period, today);
+    assert_eq!(after.total_views, before.total_views + 2, "재조회도 조회 수에는 더해야 합니다");
+    assert_eq!(after.total_visitors, before.total_visitors + 1, "방문자는 하루 한 번만 세야 합니다");
+
+    let req = series(format!("/admin/stats/site?from={today}&to={today}"));
+    let site: TimeSeries = test::call_and_read_body_json(&app, req).await;
+    assert!(site.post_id.is_none());
+    assert!(site.total_views >= after.total_views);
+
+    let req = series("/admin/stats/site?from=2026-01-15&to=2026-03-02&granularity=month".to_string());
+    let monthly: TimeSeries = test::call_and_read_body_json(&app, req).await;
+    let periods: Vec<NaiveDate> = monthly.points.iter().map(|p| p.period).collect();
+    assert_eq!(
+        periods,
+        ["2026-01-01", "2026-02-01", "2026-03-01"].map(|d| d.parse::<NaiveDate>().unwrap()),
+        "빈 구간도 0 으로 채워야 합니다"
+    );
+
+    let req = series("/admin/stats/site?from=2026-10-07&to=2026-10-13&granularity=week".to_string());
+    let weekly: TimeSeries = test::call_and_read_body_json(&app, req).await;
+    let periods: Vec<NaiveDate> = weekly.points.iter().map(|p| p.period).collect();
+    assert_eq!(periods, ["2026-10-05", "2026-10-12"].map(|d| d.parse::<NaiveDate>().unwrap()));
+
+    let req = series("/admin/stats/site?from=2026-03-01&to=2026-01-01".to_string());
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = series("/admin/stats/posts/999999".to_string());
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_referrer_report_flow() {
+    let mut config = load_config();
+    config.stats.buffer_views = false;
+    config.stats.site_hosts = "blog.example.com".to_string();
+    let pool = db::init_pool(&config.pg);
+    let post_id = 7;
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(VisitorHasher::new()))
+        .app_data(web::Data::new(BotDetector::new(&config.stats)))
+        .app_data(web::Data::new(ViewBuffer::new(&config.stats)))
+        .service(view_post)
+        .service(referrer_report)
+        .service(campaign_report);
+
+    let app = test::init_service(app).await;
+    let today = Utc::now().date_naive();
+    let report = |kind: &str| {
+        test::TestRequest::get()
+            .uri(&format!("/admin/stats/{kind}?from={today}&to={today}&post_id={post_id}"))
+            .cookie(admin_cookie(&config))
+            .to_request()
+    };
+    let source_hits = |report: &ReferrerReport, source: &str| {
+        report.sources.iter().find(|s| s.source == source).map(|s| s.hits).unwrap_or(0)
+    };
+    let host_hits = |report: &ReferrerReport, host: &str| {
+        report.referrers.iter().find(|r| r.host == host).map(|r| r.hits).unwrap_or(0)
+    };
+
+    let before: ReferrerReport = test::call_and_read_body_json(&app, report("referrers")).await;
+
+    let run = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let campaign = format!("Launch-{run}");
+    let bodies = [
+        Some(serde_json::json!({ "referrer": "https://www.google.com/search?q=rust" })),
+        Some(serde_json::json!({
+            "referrer": "https://t.co/abc",
+            "utm_source": "Twitter",
+            "utm_medium": "social",
+            "utm_campaign": campaign,
+        })),
+        Some(serde_json::json!({ "utm_source": "newsletter", "utm_campaign": campaign })),
+        Some(serde_json::json!({ "referrer": "https://www.blog.example.com/posts/1" })),
+        None,
+    ];
+    for (i, body) in bodies.into_iter().enumerate() {
+        let req = test::TestRequest::post()
+            .uri(&format!("/posts/{post_id}/view"))
+            .insert_header((header::USER_AGENT, format!("Mozilla/5.0 referrer-test/{run}/{i}")))
+            .insert_header((header::ACCEPT_LANGUAGE, "ko-KR"));
+        let req = match body {
+            Some(body) => req.set_json(body),
+            None => req,
+        };
+        let resp = test::call_service(&app, req.to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    let after: ReferrerReport = test::call_and_read_body_json(&app, report("referrers")).await;
+    assert_eq!(after.post_id, Some(post_id));
+    assert_eq!(source_hits(&after, "search"), source_hits(&before, "search") + 1);
+    assert_eq!(source_hits(&after, "social"), source_hits(&before, "social") + 1);
+    assert_eq!(source_hits(&after, "referral"), source_hits(&before, "referral") + 1, "utm_source 만 있으면 캠페인 이름으로 분류해야 합니다");
+    assert_eq!(source_hits(&after, "internal"), source_hits(&before, "internal") + 1);
+    assert_eq!(source_hits(&after, "direct"), source_hits(&before, "direct") + 1);
+    assert_eq!(host_hits(&after, "google.com"), host_hits(&before, "google.com") + 1, "호스트는 www. 를 떼고 모아야 합니다");
+    assert_eq!(host_hits(&after, "t.co"), host_hits(&before, "t.co") + 1);
+
+    let campaigns: CampaignReport = test::call_and_read_body_json(&app, report("campaigns")).await;
+    let ours: Vec<_> = campaigns.campaigns.iter().filter(|c| c.utm_campaign == campaign).collect();
+    assert_eq!(ours.len(), 2);
+    assert!(ours.iter().any(|c| c.utm_source == "twitter" && c.utm_medium == "social" && c.hits == 1), "utm_source 는 소문자로 정규화해야 합니다");
+    assert!(ours.iter().any(|c| c.utm_source == "newsletter" && c.utm_medium.is_empty()));
+
+    let req = test::TestRequest::get()
+        .uri("/admin/stats/referrers?from=2026-03-01&to=2026-01-01")
+        .cookie(admin_cookie(&config))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::get().uri("/admin/stats/campaigns").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_engagement_beacon_flow() {
+    let config = load_config();
+    let pool = db::init_pool(&config.pg);
+    let post_id = 10;
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(VisitorHasher::new()))
+        .app_data(web::Data::new(BotDetector::new(&config.stats)))
+        .service(record_engagement)
+        .service(engagement_report);
+
+    let app = test::init_service(app).await;
+    let today = Utc::now().date_naive();
+    let report = || {
+        test::TestRequest::get()
+            .uri(&format!("/admin/stats/engagement?from={today}&to={today}&post_id={post_id}"))
+            .cookie(admin_cookie(&config))
+            .to_request()
+    };
+    let totals = |report: &EngagementReport| {
+        let reads = report.reads as f64;
+        (report.reads, (report.avg_read_secs * reads).round() as i64, (report.completion_rate * reads).round() as i64)
+    };
+
+    let before: EngagementReport = test::call_and_read_body_json(&app, report()).await;
+    let run = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let beacon = |agent: String, body: &str, content_type: &str| {
+        test::TestRequest::post()
+            .uri(&format!("/posts/{post_id}/engagement"))
+            .insert_header((header::USER_AGENT, agent))
+            .insert_header((header::CONTENT_TYPE, content_type.to_string()))
+            .set_payload(body.to_string())
+            .to_request()
+    };
+
+    let reader = format!("Mozilla/5.0 engagement-test/{run}/1");
+    let resp = test::call_service(&app, beacon(reader.clone(), r#"{"read_secs":120.4,"scroll_percent":95}"#, "text/plain;charset=UTF-8")).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = test::call_service(&app, beacon(reader, r#"{"read_secs":600,"scroll_percent":100}"#, "application/json")).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT, "같은 방문자의 두 번째 비콘은 조용히 무시해야 합니다");
+
+    let skimmer = format!("Mozilla/5.0 engagement-test/{run}/2");
+    let resp = test::call_service(&app, beacon(skimmer, "read_secs=59.6&scroll_percent=40", "application/x-www-form-urlencoded")).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT, "폼 인코딩 본문도 받아야 합니다");
+
+    let idler = format!("Mozilla/5.0 engagement-test/{run}/3");
+    test::call_service(&app, beacon(idler, r#"{"read_secs":86400}"#, "text/plain")).await;
+
+    test::call_service(&app, beacon("curl/8.5.0".to_string(), r#"{"read_secs":30}"#, "application/json")).await;
+
+    let resp = test::call_service(&app, beacon(format!("Mozilla/5.0 engagement-test/{run}/4"), "hello", "text/plain")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::post()
+        .uri("/posts/999999/engagement")
+        .insert_header((header::USER_AGENT, format!("Mozilla/5.0 engagement-test/{run}/5")))
+        .set_payload(r#"{"read_secs":10}"#)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let after: EngagementReport = test::call_and_read_body_json(&app, report()).await;
+    let (reads, secs, completions) = totals(&after);
+    let (reads_before, secs_before, completions_before) = totals(&before);
+    assert_eq!(reads, reads_before + 3, "봇과 중복 비콘은 세지 않아야 합니다");
+    assert_eq!(secs, secs_before + 120 + 60 + 3600, "읽은 시간은 한 시간으로 자르고 더해야 합니다");
+    assert_eq!(completions, completions_before + 1);
+    assert_eq!(after.posts.len(), 1);
+    assert_eq!(after.posts[0].post_id, post_id);
+
+    let req = test::TestRequest::get().uri("/admin/stats/engagement").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_export_flow() {
+    let config = load_config();
+    let pool = db::init_pool(&config.pg);
+    let day: NaiveDate = "2026-01-10".parse().unwrap();
+
+    let client = pool.get().await.unwrap();
+    for (post_id, views) in [(11, 30), (12, 40)] {
+        client
+            .execute(
+                "INSERT INTO post_daily_stats (day, post_id, views, visitors, reactions) VALUES ($1, $2, $3, 20, 2)
+                 ON CONFLICT (day, post_id) DO UPDATE SET views = EXCLUDED.views, visitors = 20, reactions = 2",
+                &[&day, &post_id, &views]
+            ).await.unwrap();
+    }
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .service(export_posts)
+        .service(export_daily);
+
+    let app = test::init_service(app).await;
+    let export = |uri: &str| test::TestRequest::get().uri(uri).cookie(admin_cookie(&config)).to_request();
+
+    let resp = test::call_service(&app, export("/admin/export/daily?from=2026-01-10&to=2026-01-10&tag=serde")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap().starts_with("text/csv"));
+    let disposition = resp.headers().get(header::CONTENT_DISPOSITION).unwrap().to_str().unwrap().to_string();
+    assert!(disposition.contains("daily-2026-01-10-2026-01-10.csv"));
+    let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines, vec![
+        "day,post_id,title,views,visitors,reactions,reads,read_secs",
+        "2026-01-10,11,열한 번째 게시물,30,20,2,0,0",
+    ], "태그로 거른 글의 하루 통계만 나와야 합니다");
+
+    let resp = test::call_service(&app, export("/admin/export/daily?from=2026-01-10&to=2026-01-10&tag=Serde")).await;
+    let mixed = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    assert_eq!(mixed, body, "태그는 대소문자와 상관없이 걸러야 합니다");
+
+    let resp = test::call_service(&app, export("/admin/export/posts?format=ndjson&from=2026-01-10&to=2026-01-10&tag=serde")).await;
+    assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "application/x-ndjson");
+    let body = test::read_body(resp).await;
+    let rows: Vec<PostExport> = body
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].post.id, 11);
+    assert_eq!(rows[0].post.tags, vec!["json", "serde"]);
+    assert_eq!((rows[0].views, rows[0].visitors, rows[0].reactions_added), (30, 20, 2));
+
+    let resp = test::call_service(&app, export("/admin/export/posts?format=ndjson&from=2026-01-10&to=2026-01-10&tag=Serde")).await;
+    let mixed = test::read_body(resp).await;
+    assert_eq!(mixed, body, "태그는 대소문자와 상관없이 걸러야 합니다");
+
+    let resp = test::call_service(&app, export("/admin/export/posts?from=2026-01-10&to=2026-01-10")).await;
+    let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    let post_12 = body.lines().find(|line| line.starts_with("12,")).expect("모든 글이 나와야 합니다");
+    assert!(post_12.contains(",\"error,handling\","), "쉼표가 든 값은 따옴표로 감싸야 합니다: {post_12}");
+    assert!(post_12.ends_with(",40,20,2,0,0"));
+    assert!(body.lines().count() > 20);
+
+    let resp = test::call_service(&app, export("/admin/export/posts?format=xml")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = test::call_service(&app, export("/admin/export/daily?from=2020-01-01&to=2026-01-10")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    assert!(body.contains("1095일"), "최대 기간을 알려줘야 합니다: {body}");
+
+    let req = test::TestRequest::get().uri("/admin/export/daily").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
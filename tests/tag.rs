@@ -0,0 +1,183 @@
+use actix_web::{ test, web, App };
+use actix_web::http::StatusCode;
+use blog::blog::dto::PostDetail;
+use blog::blog::handlers::{ get_post, update_post };
+use blog::blog::related::RelatedPosts;
+use blog::db;
+use blog::tag::dto::{ TagDetail, TagSummary };
+use blog::tag::handlers::{ delete_tag, get_tag, list_tags, merge_tag, update_tag };
+use blog::tag::model::Tag;
+
+mod common;
+use common::{ admin_cookie, load_config };
+
+#[actix_web::test]
+async fn test_tag_management_flow() {
+    let config = load_config();
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(config.clone()))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(RelatedPosts::new()))
+        .service(list_tags)
+        .service(get_tag)
+        .service(get_post)
+        .service(update_post)
+        .service(update_tag)
+        .service(merge_tag)
+        .service(delete_tag);
+
+    let app = test::init_service(app).await;
+    let admin = admin_cookie(&config);
+    let run = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+
+    let set_tags = |post_id: i32, tags: Vec<String>| {
+        test::TestRequest::put()
+            .uri(&format!("/posts/{post_id}"))
+            .cookie(admin.clone())
+            .set_json(serde_json::json!({ "tags": tags }))
+            .to_request()
+    };
+    let tags_of = |post_id: i32| test::TestRequest::get().uri(&format!("/posts/{post_id}")).to_request();
+
+    let alpha = format!("alpha-{run}");
+    let beta = format!("beta-{run}");
+    let gamma = format!("gamma-{run}");
+
+    let resp = test::call_service(
+        &app,
+        set_tags(19, vec![format!("  Alpha {run} "), format!("ALPHA_{run}"), format!("Beta {run}")])
+    ).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let post: PostDetail = test::call_and_read_body_json(&app, tags_of(19)).await;
+    assert_eq!(post.post.tags, vec![alpha.clone(), beta.clone()], "대소문자와 띄어쓰기가 다른 태그는 하나로 합쳐야 합니다");
+
+    let resp = test::call_service(&app, set_tags(20, vec![beta.clone()])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // 개발 블로그에서 흔한 기호는 그대로 둡니다.
+    let sharp = format!("c#{run}");
+    let resp = test::call_service(&app, set_tags(20, vec![beta.clone(), format!("C#{run}")])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let post: PostDetail = test::call_and_read_body_json(&app, tags_of(20)).await;
+    assert_eq!(post.post.tags, vec![beta.clone(), sharp.clone()]);
+    let req = test::TestRequest::get().uri(&format!("/tags/C%23{run}")).to_request();
+    let detail: TagDetail = test::call_and_read_body_json(&app, req).await;
+    assert_eq!((detail.summary.tag.slug.as_str(), detail.summary.post_count), (sharp.as_str(), 1));
+    let req = test::TestRequest::delete().uri(&format!("/admin/tags/c%23{run}")).cookie(admin.clone()).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let post: PostDetail = test::call_and_read_body_json(&app, tags_of(20)).await;
+    assert_eq!(post.post.tags, vec![beta.clone()]);
+
+    let req = test::TestRequest::get().uri("/tags").to_request();
+    let tags: Vec<TagSummary> = test::call_and_read_body_json(&app, req).await;
+    let count = |slug: &str| tags.iter().find(|t| t.tag.slug == slug).map(|t| t.post_count);
+    assert_eq!((count(&alpha), count(&beta)), (Some(1), Some(2)));
+    let alpha_tag = tags.iter().find(|t| t.tag.slug == alpha).unwrap();
+    assert_eq!(alpha_tag.tag.name, format!("Alpha {run}"), "처음 입력한 표기를 이름으로 써야 합니다");
+
+    let edit = |slug: &str, body: serde_json::Value| {
+        test::TestRequest::put()
+            .uri(&format!("/admin/tags/{slug}"))
+            .cookie(admin.clone())
+            .set_json(body)
+            .to_request()
+    };
+    let resp = test::call_service(&app, edit(&alpha, serde_json::json!({ "color": "orange" }))).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = test::call_service(&app, edit(&alpha, serde_json::json!({ "slug": beta }))).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "이미 있는 태그로 바꾸려면 병합해야 합니다");
+
+    let renamed: Tag = test::call_and_read_body_json(
+        &app,
+        edit(&alpha, serde_json::json!({ "slug": format!("Gamma {run}"), "name": "감마", "description": "설명", "color": "#FF8800" }))
+    ).await;
+    assert_eq!(renamed.slug, gamma);
+    assert_eq!((renamed.name.as_str(), renamed.color.as_str()), ("감마", "#ff8800"));
+    let post: PostDetail = test::call_and_read_body_json(&app, tags_of(19)).await;
+    assert_eq!(post.post.tags, vec![gamma.clone(), beta.clone()], "이름을 바꾸면 글에도 반영되어야 합니다");
+
+    let merge = |source: &str, into: &str| {
+        test::TestRequest::post()
+            .uri(&format!("/admin/tags/{source}/merge"))
+            .cookie(admin.clone())
+            .set_json(serde_json::json!({ "into": into }))
+            .to_request()
+    };
+    let resp = test::call_service(&app, merge(&gamma, &gamma)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = test::call_service(&app, merge(&alpha, &beta)).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let target: Tag = test::call_and_read_body_json(&app, merge(&gamma, &beta)).await;
+    assert_eq!(target.slug, beta);
+    let post: PostDetail = test::call_and_read_body_json(&app, tags_of(19)).await;
+    assert_eq!(post.post.tags, vec![beta.clone()], "병합 후 중복 태그는 하나만 남아야 합니다");
+    let post: PostDetail = test::call_and_read_body_json(&app, tags_of(20)).await;
+    assert_eq!(post.post.tags, vec![beta.clone()]);
+
+    let remove = |slug: &str| {
+        test::TestRequest::delete().uri(&format!("/admin/tags/{slug}")).cookie(admin.clone()).to_request()
+    };
+    let resp = test::call_service(&app, remove(&beta)).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = test::call_service(&app, remove(&beta)).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let post: PostDetail = test::call_and_read_body_json(&app, tags_of(19)).await;
+    assert!(post.post.tags.is_empty(), "삭제한 태그는 모든 글에서 떼어야 합니다");
+
+    let req = test::TestRequest::get().uri("/tags").to_request();
+    let tags: Vec<TagSummary> = test::call_and_read_body_json(&app, req).await;
+    assert!(!tags.iter().any(|t| t.tag.slug.ends_with(&run.to_string())));
+
+    let resp = test::call_service(&app, set_tags(19, vec!["array".into(), "type".into()])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = test::call_service(&app, set_tags(20, vec!["structure".into(), "design".into()])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::delete().uri("/admin/tags/array").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_tag_list_and_detail() {
+    let config = load_config();
+    let pool = db::init_pool(&config.pg);
+
+    let app = App::new()
+        .app_data(web::Data::new(pool.clone()))
+        .service(list_tags)
+        .service(get_tag);
+
+    let app = test::init_service(app).await;
+
+    let req = test::TestRequest::get().uri("/tags?sort=count&limit=2").to_request();
+    let tags: Vec<TagSummary> = test::call_and_read_body_json(&app, req).await;
+    let top: Vec<(&str, i64)> = tags.iter().map(|t| (t.tag.slug.as_str(), t.post_count)).collect();
+    assert_eq!(top, vec![("actix", 3), ("rust", 3)]);
+    assert!(tags.iter().all(|t| t.latest_post_at.is_some()));
+
+    let req = test::TestRequest::get().uri("/tags").to_request();
+    let tags: Vec<TagSummary> = test::call_and_read_body_json(&app, req).await;
+    let names: Vec<&str> = tags.iter().map(|t| t.tag.name.as_str()).collect();
+    let mut sorted = names.clone();
+    sorted.sort_unstable();
+    assert_eq!(names, sorted, "기본은 이름순입니다");
+
+    let req = test::TestRequest::get().uri("/tags?sort=popular").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::get().uri("/tags/Rust?pageSize=2").to_request();
+    let detail: TagDetail = test::call_and_read_body_json(&app, req).await;
+    assert_eq!((detail.summary.tag.slug.as_str(), detail.summary.post_count), ("rust", 3));
+    assert_eq!(detail.posts.len(), 2);
+    assert!(detail.posts.iter().all(|p| p.tags.contains(&"rust".to_string())));
+
+    let req = test::TestRequest::get().uri("/tags/no-such-tag").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}